@@ -0,0 +1,100 @@
+//! Steps a million-cell elementary automaton with each storage and prints the steps per second.
+//!
+//! `cargo bench --bench elementary`
+
+use doodle::automata::bitrow::BitRow;
+use doodle::automata::elementary::{Row, Rule};
+use doodle::automata::Boundary;
+use nannou::geom::Point2;
+use rand::{Rng, SeedableRng};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const CELLS: usize = 1_000_000;
+
+/// How long each storage is stepped for.
+const BUDGET: Duration = Duration::from_secs(2);
+
+/// A cell as rule_30's lattice stored it: its position on screen, its state and its colour.
+struct Point {
+    _coord: Point2,
+    state: bool,
+    _color: u8,
+}
+
+/// rule_30's lattice before `Row`, with its tick as it was: in place and hard-coding rule 30, so
+/// that every cell reads a left neighbour already stepped. Wrong, but the cost to beat.
+struct Lattice {
+    points: Vec<Point>,
+}
+
+impl Lattice {
+    fn state_at(&self, idx: usize) -> bool {
+        self.points.get(idx).map(|val| val.state).unwrap_or(false)
+    }
+
+    fn tick(&mut self) {
+        for idx in 0..self.points.len() {
+            let new_val = match (
+                self.state_at(if idx == 0 { 0 } else { idx - 1 }),
+                self.state_at(idx),
+                self.state_at(idx + 1),
+            ) {
+                (true, true, true) => false,
+                (true, true, false) => false,
+                (true, false, true) => false,
+                (true, false, false) => true,
+                (false, true, true) => true,
+                (false, true, false) => true,
+                (false, false, true) => true,
+                (false, false, false) => false,
+            };
+            self.points[idx].state = new_val;
+        }
+    }
+}
+
+/// Call `step` until `BUDGET` runs out and print its rate.
+fn bench(name: &str, mut step: impl FnMut()) {
+    let start = Instant::now();
+    let mut steps = 0u64;
+
+    while start.elapsed() < BUDGET {
+        step();
+        steps += 1;
+    }
+
+    let rate = steps as f64 / start.elapsed().as_secs_f64();
+    println!("{:<12} {:>10.1} steps/s", name, rate);
+}
+
+fn main() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(30);
+    let cells: Vec<bool> = (0..CELLS).map(|_| rng.gen()).collect();
+    let rule = Rule::new(30);
+
+    println!("rule 30 on {} cells", CELLS);
+
+    let points = cells
+        .iter()
+        .enumerate()
+        .map(|(idx, &state)| Point {
+            _coord: Point2 {
+                x: idx as f32,
+                y: 0.0,
+            },
+            state,
+            _color: 0,
+        })
+        .collect();
+    let mut lattice = Lattice { points };
+    bench("Vec<Point>", || black_box(&mut lattice).tick());
+
+    let mut row = Row::from_cells(cells.clone());
+    bench("Row", || black_box(&mut row).step(rule, Boundary::Dead));
+
+    let mut packed = BitRow::from_cells(&cells);
+    bench("BitRow", || {
+        black_box(&mut packed).step(rule, Boundary::Dead)
+    });
+}
@@ -0,0 +1,157 @@
+//! What lies past the edges of a finite automaton.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// How a neighbour lookup past either end of a row is answered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// The row wraps around: past the right end is the left end.
+    Periodic,
+    /// Every cell past the ends is dead.
+    Dead,
+    /// Every cell past the ends is alive.
+    Alive,
+    /// The row is mirrored at its ends: the cell just past an end is the end cell itself.
+    Reflective,
+}
+
+/// Where the state of a cell looked up through a `Boundary` comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup {
+    /// The cell at this index, inside the row.
+    Cell(usize),
+    /// A cell past the ends, with a fixed state.
+    Fixed(bool),
+}
+
+impl Boundary {
+    pub const ALL: [Boundary; 4] = [
+        Boundary::Periodic,
+        Boundary::Dead,
+        Boundary::Alive,
+        Boundary::Reflective,
+    ];
+
+    /// Resolve `idx`, which may be past either end of a row of `len` cells.
+    pub fn resolve(self, idx: isize, len: usize) -> Lookup {
+        let len = len as isize;
+
+        if 0 <= idx && idx < len {
+            return Lookup::Cell(idx as usize);
+        }
+
+        match self {
+            Boundary::Periodic => Lookup::Cell(idx.rem_euclid(len) as usize),
+            Boundary::Dead => Lookup::Fixed(false),
+            Boundary::Alive => Lookup::Fixed(true),
+            Boundary::Reflective => {
+                let folded = idx.rem_euclid(2 * len);
+                let mirrored = if folded < len {
+                    folded
+                } else {
+                    2 * len - 1 - folded
+                };
+                Lookup::Cell(mirrored as usize)
+            }
+        }
+    }
+
+    /// The state of the cell at `idx` of `cells`, or past their ends.
+    pub fn state(self, cells: &[bool], idx: isize) -> bool {
+        match self.resolve(idx, cells.len()) {
+            Lookup::Cell(idx) => cells[idx],
+            Lookup::Fixed(state) => state,
+        }
+    }
+}
+
+impl FromStr for Boundary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "periodic" => Ok(Boundary::Periodic),
+            "dead" => Ok(Boundary::Dead),
+            "alive" => Ok(Boundary::Alive),
+            "reflective" => Ok(Boundary::Reflective),
+            other => Err(format!(
+                "expected periodic, dead, alive or reflective, got {:?}",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Boundary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Boundary::Periodic => "periodic",
+            Boundary::Dead => "dead",
+            Boundary::Alive => "alive",
+            Boundary::Reflective => "reflective",
+        };
+
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEN: usize = 4;
+
+    #[test]
+    fn inside_the_row_is_the_cell_itself() {
+        for &boundary in &Boundary::ALL {
+            for idx in 0..LEN {
+                assert_eq!(boundary.resolve(idx as isize, LEN), Lookup::Cell(idx));
+            }
+        }
+    }
+
+    #[test]
+    fn periodic_wraps_around() {
+        let boundary = Boundary::Periodic;
+        assert_eq!(boundary.resolve(-1, LEN), Lookup::Cell(3));
+        assert_eq!(boundary.resolve(4, LEN), Lookup::Cell(0));
+        assert_eq!(boundary.resolve(-5, LEN), Lookup::Cell(3));
+        assert_eq!(boundary.resolve(9, LEN), Lookup::Cell(1));
+    }
+
+    #[test]
+    fn dead_is_always_false() {
+        let cells = [true; LEN];
+        assert!(!Boundary::Dead.state(&cells, -1));
+        assert!(!Boundary::Dead.state(&cells, 4));
+        assert!(!Boundary::Dead.state(&cells, 100));
+    }
+
+    #[test]
+    fn alive_is_always_true() {
+        let cells = [false; LEN];
+        assert!(Boundary::Alive.state(&cells, -1));
+        assert!(Boundary::Alive.state(&cells, 4));
+        assert!(Boundary::Alive.state(&cells, -100));
+    }
+
+    #[test]
+    fn reflective_mirrors_the_ends() {
+        let boundary = Boundary::Reflective;
+        assert_eq!(boundary.resolve(-1, LEN), Lookup::Cell(0));
+        assert_eq!(boundary.resolve(-2, LEN), Lookup::Cell(1));
+        assert_eq!(boundary.resolve(4, LEN), Lookup::Cell(3));
+        assert_eq!(boundary.resolve(5, LEN), Lookup::Cell(2));
+        // Past a whole mirrored copy, the row starts over.
+        assert_eq!(boundary.resolve(8, LEN), Lookup::Cell(0));
+    }
+
+    #[test]
+    fn parses_its_own_display() {
+        for &boundary in &Boundary::ALL {
+            assert_eq!(boundary.to_string().parse::<Boundary>(), Ok(boundary));
+        }
+        assert!("wraparound".parse::<Boundary>().is_err());
+    }
+}
@@ -0,0 +1,241 @@
+//! Elementary cellular automata: one row of cells, each next state a function of the cell and its
+//! two neighbours.
+
+use super::Boundary;
+use std::fmt;
+
+/// An elementary rule, decoded from its Wolfram code.
+///
+/// Bit `n` of the code is the next state of a cell whose neighbourhood, read as the three bits
+/// `left center right`, is `n`. Rule 30 is `0b00011110`: `100`, `011`, `010` and `001` give a live
+/// cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    number: u8,
+    /// The next state for each neighbourhood, indexed as in the code.
+    table: [bool; 8],
+}
+
+impl Rule {
+    /// Rules worth cycling through: chaotic, fractal, universal, traffic.
+    pub const NOTABLE: &'static [u8] = &[30, 45, 73, 90, 105, 110, 150, 184];
+
+    pub fn new(number: u8) -> Self {
+        let mut table = [false; 8];
+
+        for (neighbourhood, next) in table.iter_mut().enumerate() {
+            *next = number >> neighbourhood & 1 == 1;
+        }
+
+        Rule { number, table }
+    }
+
+    /// The Wolfram code.
+    pub fn number(self) -> u8 {
+        self.number
+    }
+
+    pub fn table(self) -> [bool; 8] {
+        self.table
+    }
+
+    /// The next state of `center`.
+    pub fn apply(self, left: bool, center: bool, right: bool) -> bool {
+        self.table[(left as usize) << 2 | (center as usize) << 1 | right as usize]
+    }
+
+    /// The notable rule after this one, wrapping around.
+    pub fn next_notable(self) -> Self {
+        let next = Rule::NOTABLE
+            .iter()
+            .find(|&&number| number > self.number)
+            .unwrap_or(&Rule::NOTABLE[0]);
+
+        Rule::new(*next)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rule {}", self.number)
+    }
+}
+
+/// A row of cells, and the buffer its next generation is computed into.
+#[derive(Debug, Clone, Default)]
+pub struct Row {
+    cells: Vec<bool>,
+    next: Vec<bool>,
+}
+
+impl Row {
+    /// `len` dead cells.
+    pub fn new(len: usize) -> Self {
+        Row::from_cells(vec![false; len])
+    }
+
+    pub fn from_cells(cells: Vec<bool>) -> Self {
+        Row {
+            next: vec![false; cells.len()],
+            cells,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn cells(&self) -> &[bool] {
+        &self.cells
+    }
+
+    pub fn set(&mut self, idx: usize, state: bool) {
+        self.cells[idx] = state;
+    }
+
+    pub fn toggle(&mut self, idx: usize) {
+        self.cells[idx] = !self.cells[idx];
+    }
+
+    /// Replace the row with its next generation under `rule`, with the neighbours past the ends
+    /// given by `boundary`. Every cell is computed from the current generation, never from an
+    /// already updated neighbour.
+    pub fn step(&mut self, rule: Rule, boundary: Boundary) {
+        let cells = &self.cells;
+
+        for (idx, next) in self.next.iter_mut().enumerate() {
+            let idx = idx as isize;
+            *next = rule.apply(
+                boundary.state(cells, idx - 1),
+                boundary.state(cells, idx),
+                boundary.state(cells, idx + 1),
+            );
+        }
+
+        std::mem::swap(&mut self.cells, &mut self.next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A row of `len` cells with only the middle one alive.
+    fn single_seed(len: usize) -> Row {
+        let mut row = Row::new(len);
+        row.set(len / 2, true);
+        row
+    }
+
+    fn render(row: &Row) -> String {
+        row.cells()
+            .iter()
+            .map(|&state| if state { '#' } else { '.' })
+            .collect()
+    }
+
+    fn generations(rule: u8, len: usize, count: usize) -> Vec<String> {
+        let mut row = single_seed(len);
+        let mut out = vec![render(&row)];
+
+        for _ in 1..count {
+            row.step(Rule::new(rule), Boundary::Dead);
+            out.push(render(&row));
+        }
+
+        out
+    }
+
+    #[test]
+    fn rule_table_decodes_the_wolfram_code() {
+        let table = Rule::new(30).table();
+        assert_eq!(table, [false, true, true, true, true, false, false, false]);
+
+        let rule = Rule::new(30);
+        assert!(rule.apply(true, false, false));
+        assert!(!rule.apply(true, true, true));
+        assert!(!rule.apply(false, false, false));
+
+        assert_eq!(Rule::new(0).table(), [false; 8]);
+        assert_eq!(Rule::new(255).table(), [true; 8]);
+    }
+
+    #[test]
+    fn rule_30_from_a_single_cell() {
+        assert_eq!(
+            generations(30, 15, 8),
+            vec![
+                ".......#.......",
+                "......###......",
+                ".....##..#.....",
+                "....##.####....",
+                "...##..#...#...",
+                "..##.####.###..",
+                ".##..#....#..#.",
+                "##.####..######",
+            ]
+        );
+    }
+
+    #[test]
+    fn rule_30_center_column() {
+        // OEIS A051023.
+        let expected = [
+            1, 1, 0, 1, 1, 1, 0, 0, 1, 1, 0, 0, 0, 1, 0, 1, 1, 0, 0, 1, 0, 0, 1, 1, 1,
+        ];
+
+        let len = 2 * expected.len() + 1;
+        let mut row = single_seed(len);
+        let mut column = Vec::new();
+
+        for _ in 0..expected.len() {
+            column.push(row.cells()[len / 2] as u8);
+            row.step(Rule::new(30), Boundary::Dead);
+        }
+
+        assert_eq!(column, expected);
+    }
+
+    #[test]
+    fn rule_90_draws_a_sierpinski_triangle() {
+        assert_eq!(
+            generations(90, 9, 5),
+            vec![
+                "....#....",
+                "...#.#...",
+                "..#...#..",
+                ".#.#.#.#.",
+                "#.......#",
+            ]
+        );
+    }
+
+    /// Rule 30 from a row with only its first cell alive, for each boundary.
+    #[test]
+    fn rule_30_at_the_boundaries() {
+        let expected = [
+            (Boundary::Dead, "##..."),
+            (Boundary::Alive, ".#..#"),
+            (Boundary::Periodic, "##..#"),
+            (Boundary::Reflective, ".#..."),
+        ];
+
+        for &(boundary, next) in &expected {
+            let mut row = Row::new(5);
+            row.set(0, true);
+            row.step(Rule::new(30), boundary);
+            assert_eq!(render(&row), next, "{}", boundary);
+        }
+    }
+
+    #[test]
+    fn next_notable_wraps_around() {
+        assert_eq!(Rule::new(30).next_notable().number(), 45);
+        assert_eq!(Rule::new(31).next_notable().number(), 45);
+        assert_eq!(Rule::new(184).next_notable().number(), 30);
+    }
+}
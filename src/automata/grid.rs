@@ -0,0 +1,303 @@
+//! A rectangle of cells and how it is glued at its edges.
+
+use super::boundary::{Boundary, Lookup};
+use std::fmt;
+use std::str::FromStr;
+
+/// How the edges of a `Grid` connect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topology {
+    /// Past the right edge is the left edge, and past the top the bottom.
+    Torus,
+    /// There is nothing past the edges.
+    Bounded,
+    /// Past the right edge is the left edge, but past the top is the bottom mirrored left to
+    /// right: whatever crosses it comes back with its left and right swapped.
+    KleinBottle,
+}
+
+/// Where a step from one cell to a neighbouring one lands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub x: usize,
+    pub y: usize,
+    /// Whether the step crossed a mirrored edge, swapping left and right.
+    pub mirrored: bool,
+}
+
+impl Topology {
+    pub const ALL: [Topology; 3] = [Topology::Torus, Topology::Bounded, Topology::KleinBottle];
+}
+
+impl FromStr for Topology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "torus" => Ok(Topology::Torus),
+            "bounded" => Ok(Topology::Bounded),
+            "klein-bottle" => Ok(Topology::KleinBottle),
+            other => Err(format!(
+                "expected torus, bounded or klein-bottle, got {:?}",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Topology::Torus => "torus",
+            Topology::Bounded => "bounded",
+            Topology::KleinBottle => "klein-bottle",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// `columns` by `rows` cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    columns: usize,
+    rows: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid with every cell set to `value`.
+    pub fn new(columns: usize, rows: usize, value: T) -> Self {
+        Grid {
+            columns,
+            rows,
+            cells: vec![value; columns * rows],
+        }
+    }
+
+    pub fn fill(&mut self, value: T) {
+        for cell in self.cells.iter_mut() {
+            *cell = value.clone();
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cells, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// The index in `cells` of the cell in column `x` and row `y`.
+    pub fn index(&self, x: usize, y: usize) -> usize {
+        debug_assert!(
+            x < self.columns && y < self.rows,
+            "({}, {}) is off the grid",
+            x,
+            y
+        );
+        y * self.columns + x
+    }
+
+    /// The column and row of the cell at `idx` in `cells`.
+    pub fn coords(&self, idx: usize) -> (usize, usize) {
+        (idx % self.columns, idx / self.columns)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> &T {
+        &self.cells[self.index(x, y)]
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> &mut T {
+        let idx = self.index(x, y);
+        &mut self.cells[idx]
+    }
+
+    /// The cell at `(x, y)`, which may be past the edges, as `boundary` answers for them:
+    /// `Err` with the fixed state of cells past dead or alive edges.
+    pub fn resolve(&self, x: isize, y: isize, boundary: Boundary) -> Result<&T, bool> {
+        match (
+            boundary.resolve(x, self.columns),
+            boundary.resolve(y, self.rows),
+        ) {
+            (Lookup::Cell(x), Lookup::Cell(y)) => Ok(self.get(x, y)),
+            (Lookup::Fixed(state), _) | (_, Lookup::Fixed(state)) => Err(state),
+        }
+    }
+
+    /// The cell `dx` columns and `dy` rows away from `(x, y)`, at most one of each, through the
+    /// edges as `topology` connects them. `None` past the edges of a bounded grid.
+    pub fn step(
+        &self,
+        x: usize,
+        y: usize,
+        dx: isize,
+        dy: isize,
+        topology: Topology,
+    ) -> Option<Step> {
+        let wrap = |value: usize, delta: isize, len: usize| -> (usize, bool) {
+            match (value, delta) {
+                (0, -1) => (len - 1, true),
+                (value, 1) if value + 1 == len => (0, true),
+                (value, delta) => ((value as isize + delta) as usize, false),
+            }
+        };
+
+        let (x, crossed_x) = wrap(x, dx, self.columns);
+        let (y, crossed_y) = wrap(y, dy, self.rows);
+
+        match topology {
+            Topology::Torus => Some(Step {
+                x,
+                y,
+                mirrored: false,
+            }),
+            Topology::Bounded if crossed_x || crossed_y => None,
+            Topology::Bounded => Some(Step {
+                x,
+                y,
+                mirrored: false,
+            }),
+            Topology::KleinBottle if crossed_y => Some(Step {
+                x: self.columns - 1 - x,
+                y,
+                mirrored: true,
+            }),
+            Topology::KleinBottle => Some(Step {
+                x,
+                y,
+                mirrored: false,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: usize = 5;
+    const ROWS: usize = 3;
+
+    fn grid() -> Grid<u8> {
+        Grid::new(COLUMNS, ROWS, 0)
+    }
+
+    fn step(x: usize, y: usize, mirrored: bool) -> Option<Step> {
+        Some(Step { x, y, mirrored })
+    }
+
+    #[test]
+    fn index_and_coords_agree() {
+        let grid = grid();
+
+        for idx in 0..grid.len() {
+            let (x, y) = grid.coords(idx);
+            assert!(x < COLUMNS && y < ROWS);
+            assert_eq!(grid.index(x, y), idx);
+        }
+        assert_eq!(grid.coords(COLUMNS), (0, 1));
+        assert_eq!(grid.index(COLUMNS - 1, ROWS - 1), COLUMNS * ROWS - 1);
+    }
+
+    #[test]
+    fn resolve_through_boundaries() {
+        let mut grid = grid();
+        *grid.get_mut(0, 0) = 1;
+        *grid.get_mut(COLUMNS - 1, ROWS - 1) = 2;
+
+        assert_eq!(grid.resolve(0, 0, Boundary::Dead), Ok(&1));
+        assert_eq!(grid.resolve(-1, -1, Boundary::Periodic), Ok(&2));
+        assert_eq!(grid.resolve(-1, 0, Boundary::Reflective), Ok(&1));
+        assert_eq!(
+            grid.resolve(COLUMNS as isize, 0, Boundary::Dead),
+            Err(false)
+        );
+        assert_eq!(grid.resolve(0, -1, Boundary::Alive), Err(true));
+    }
+
+    #[test]
+    fn inside_every_topology_agrees() {
+        for &topology in &Topology::ALL {
+            assert_eq!(grid().step(2, 1, 1, 0, topology), step(3, 1, false));
+            assert_eq!(grid().step(2, 1, -1, -1, topology), step(1, 0, false));
+            assert_eq!(grid().step(0, 0, 1, 1, topology), step(1, 1, false));
+            assert_eq!(grid().step(4, 2, 0, -1, topology), step(4, 1, false));
+        }
+    }
+
+    #[test]
+    fn torus_wraps_at_every_edge() {
+        let topology = Topology::Torus;
+        assert_eq!(grid().step(0, 1, -1, 0, topology), step(4, 1, false));
+        assert_eq!(grid().step(4, 1, 1, 0, topology), step(0, 1, false));
+        assert_eq!(grid().step(1, 0, 0, -1, topology), step(1, 2, false));
+        assert_eq!(grid().step(1, 2, 0, 1, topology), step(1, 0, false));
+        assert_eq!(grid().step(4, 2, 1, 1, topology), step(0, 0, false));
+    }
+
+    #[test]
+    fn bounded_stops_at_every_edge() {
+        let topology = Topology::Bounded;
+        assert_eq!(grid().step(0, 1, -1, 0, topology), None);
+        assert_eq!(grid().step(4, 1, 1, 0, topology), None);
+        assert_eq!(grid().step(1, 0, 0, -1, topology), None);
+        assert_eq!(grid().step(1, 2, 0, 1, topology), None);
+        assert_eq!(grid().step(4, 1, 1, 1, topology), None);
+        assert_eq!(grid().step(4, 1, 0, 1, topology), step(4, 2, false));
+    }
+
+    #[test]
+    fn klein_bottle_mirrors_across_the_top_and_bottom() {
+        let topology = Topology::KleinBottle;
+        assert_eq!(grid().step(0, 1, -1, 0, topology), step(4, 1, false));
+        assert_eq!(grid().step(4, 1, 1, 0, topology), step(0, 1, false));
+        assert_eq!(grid().step(1, 0, 0, -1, topology), step(3, 2, true));
+        assert_eq!(grid().step(1, 2, 0, 1, topology), step(3, 0, true));
+        assert_eq!(grid().step(2, 2, 0, 1, topology), step(2, 0, true));
+        assert_eq!(grid().step(4, 2, 1, 1, topology), step(4, 0, true));
+    }
+
+    /// Going around the bottle twice vertically comes back unmirrored.
+    #[test]
+    fn klein_bottle_twice_around_is_the_identity() {
+        let grid = grid();
+        let (mut x, mut y, mut mirrored) = (1, 0, false);
+
+        for _ in 0..2 * ROWS {
+            let next = grid.step(x, y, 0, 1, Topology::KleinBottle).unwrap();
+            x = next.x;
+            y = next.y;
+            mirrored ^= next.mirrored;
+        }
+
+        assert_eq!((x, y, mirrored), (1, 0, false));
+    }
+
+    #[test]
+    fn one_by_one() {
+        let grid = Grid::new(1, 1, ());
+        assert_eq!(grid.step(0, 0, 1, 0, Topology::Torus), step(0, 0, false));
+        assert_eq!(
+            grid.step(0, 0, 0, -1, Topology::KleinBottle),
+            step(0, 0, true)
+        );
+        assert_eq!(grid.step(0, 0, 0, 1, Topology::Bounded), None);
+    }
+}
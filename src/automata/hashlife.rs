@@ -0,0 +1,659 @@
+//! Hashlife: Life-like automata on an unbounded plane, stepped through a memoised quadtree.
+//!
+//! The universe is a quadtree whose identical subtrees are shared, so a pattern costs memory for
+//! its distinct blocks only. The future of the centre of every block is cached, which lets
+//! regular patterns skip over huge numbers of generations at once.
+
+use super::life::{Board, LifeRule, Neighbourhood};
+use std::collections::HashMap;
+
+/// The index of a node in `Hashlife::nodes`.
+type NodeId = u32;
+
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+
+/// The highest level of the root, whose coordinates still fit in an `i64`.
+const MAX_LEVEL: u8 = 62;
+
+/// How many nodes there may be before the ones the root does not use are dropped.
+const NODE_LIMIT: usize = 1 << 21;
+
+/// A square block of `2^level` cells on a side. Level 0 nodes are single cells.
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    level: u8,
+    /// North west, north east, south west and south east quadrants, meaningless at level 0.
+    children: [NodeId; 4],
+    population: u64,
+}
+
+/// A Life-like universe without edges.
+///
+/// Coordinates grow to the right and downwards, as on a `Board`.
+#[derive(Debug, Clone)]
+pub struct Hashlife {
+    rule: LifeRule,
+    neighbourhood: Neighbourhood,
+    /// Every distinct node, until there are more than `node_limit` of them.
+    nodes: Vec<Node>,
+    node_limit: usize,
+    /// The node with given children, so that equal blocks are the same node.
+    table: HashMap<[NodeId; 4], NodeId>,
+    /// The centre of a node `2^j` generations later, by node and `j`.
+    successors: HashMap<(NodeId, u8), NodeId>,
+    /// The empty node of each level.
+    empty: Vec<NodeId>,
+    root: NodeId,
+    /// The coordinates of the top left cell of `root`.
+    origin: (i64, i64),
+    generation: u64,
+}
+
+impl Hashlife {
+    /// The most generations `step` covers at once.
+    pub const MAX_STEP: u64 = 1 << 32;
+
+    /// An empty universe following `rule` in `neighbourhood`. Rules with B0 are refused: under
+    /// them the empty plane is not empty a generation later.
+    pub fn new(rule: LifeRule, neighbourhood: Neighbourhood) -> Result<Self, String> {
+        check(rule)?;
+
+        let leaf = |population| Node {
+            level: 0,
+            children: [DEAD; 4],
+            population,
+        };
+
+        let mut universe = Hashlife {
+            rule,
+            neighbourhood,
+            nodes: vec![leaf(0), leaf(1)],
+            node_limit: NODE_LIMIT,
+            table: HashMap::new(),
+            successors: HashMap::new(),
+            empty: vec![DEAD],
+            root: DEAD,
+            origin: (0, 0),
+            generation: 0,
+        };
+
+        universe.root = universe.empty_node(3);
+        Ok(universe)
+    }
+
+    pub fn rule(&self) -> LifeRule {
+        self.rule
+    }
+
+    /// Follow `rule` from now on. The cached futures were computed with the old one, so they are
+    /// dropped. Rules with B0 are refused, as by `new`.
+    pub fn set_rule(&mut self, rule: LifeRule) -> Result<(), String> {
+        check(rule)?;
+        self.rule = rule;
+        self.successors.clear();
+        Ok(())
+    }
+
+    /// Generations since the universe was created.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The number of live cells.
+    pub fn population(&self) -> u64 {
+        self.node(self.root).population
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> bool {
+        let (mut x, mut y) = match self.local(x, y) {
+            Some(local) => local,
+            None => return false,
+        };
+
+        let mut node = self.root;
+        let mut half = self.size() / 2;
+
+        while self.node(node).level > 0 {
+            let quadrant = (y >= half) as usize * 2 + (x >= half) as usize;
+            node = self.node(node).children[quadrant];
+            x %= half.max(1);
+            y %= half.max(1);
+            half /= 2;
+        }
+
+        node == ALIVE
+    }
+
+    pub fn set(&mut self, x: i64, y: i64, alive: bool) {
+        loop {
+            if let Some((local_x, local_y)) = self.local(x, y) {
+                self.root = self.set_in(self.root, local_x, local_y, alive);
+                return;
+            }
+
+            self.expand();
+        }
+    }
+
+    /// Advance the universe by `generations`, at most `MAX_STEP`.
+    ///
+    /// Panics if a pattern grows or travels too far for `i64` coordinates.
+    pub fn step(&mut self, generations: u64) {
+        assert!(
+            generations <= Hashlife::MAX_STEP,
+            "cannot step {} generations at once, at most {}",
+            generations,
+            Hashlife::MAX_STEP
+        );
+
+        for j in 0..=32 {
+            if generations >> j & 1 == 1 {
+                self.step_power_of_two(j);
+
+                if self.nodes.len() > self.node_limit {
+                    self.collect_garbage();
+                }
+            }
+        }
+    }
+
+    /// The cells of the `columns` by `rows` window whose top left cell is at `(left, top)`. Only
+    /// the parts of the quadtree that overlap the window and hold live cells are visited.
+    pub fn window(&self, left: i64, top: i64, columns: usize, rows: usize) -> Board {
+        let mut board = Board::new(columns, rows);
+        let window = (left, top, left + columns as i64, top + rows as i64);
+        self.fill(&mut board, window, self.root, self.origin);
+        board
+    }
+
+    fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id as usize]
+    }
+
+    fn size(&self) -> i64 {
+        1 << self.node(self.root).level
+    }
+
+    /// `(x, y)` from the top left corner of the root, if the root covers it.
+    fn local(&self, x: i64, y: i64) -> Option<(i64, i64)> {
+        let size = self.size();
+        let x = x.checked_sub(self.origin.0)?;
+        let y = y.checked_sub(self.origin.1)?;
+
+        if 0 <= x && x < size && 0 <= y && y < size {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    /// Start over with only the nodes the root is made of, and no cached futures.
+    fn collect_garbage(&mut self) {
+        let leaves = self.nodes[..=ALIVE as usize].to_vec();
+        let old = std::mem::replace(&mut self.nodes, leaves);
+        self.table.clear();
+        self.successors.clear();
+        self.empty = vec![DEAD];
+
+        let mut moved = HashMap::new();
+        self.root = self.copy(&old, self.root, &mut moved);
+    }
+
+    /// The node `id` of `old`, rebuilt in `nodes`.
+    fn copy(&mut self, old: &[Node], id: NodeId, moved: &mut HashMap<NodeId, NodeId>) -> NodeId {
+        if id <= ALIVE {
+            return id;
+        }
+        if let Some(&new) = moved.get(&id) {
+            return new;
+        }
+
+        let mut children = old[id as usize].children;
+        for child in children.iter_mut() {
+            *child = self.copy(old, *child, moved);
+        }
+
+        let new = self.join(children);
+        moved.insert(id, new);
+        new
+    }
+
+    /// The node with these quadrants, created if it does not exist yet.
+    fn join(&mut self, children: [NodeId; 4]) -> NodeId {
+        if let Some(&id) = self.table.get(&children) {
+            return id;
+        }
+
+        let node = Node {
+            level: self.node(children[0]).level + 1,
+            children,
+            population: children.iter().map(|&id| self.node(id).population).sum(),
+        };
+
+        let id = self.nodes.len() as NodeId;
+        self.nodes.push(node);
+        self.table.insert(children, id);
+        id
+    }
+
+    fn empty_node(&mut self, level: u8) -> NodeId {
+        while self.empty.len() <= usize::from(level) {
+            let below = *self.empty.last().unwrap();
+            let empty = self.join([below; 4]);
+            self.empty.push(empty);
+        }
+
+        self.empty[usize::from(level)]
+    }
+
+    /// Double the size of the root, keeping its contents at the centre.
+    fn expand(&mut self) {
+        let root = *self.node(self.root);
+        assert!(
+            root.level < MAX_LEVEL,
+            "the pattern has outgrown the universe"
+        );
+
+        let empty = self.empty_node(root.level - 1);
+        let [nw, ne, sw, se] = root.children;
+
+        let children = [
+            self.join([empty, empty, empty, nw]),
+            self.join([empty, empty, ne, empty]),
+            self.join([empty, sw, empty, empty]),
+            self.join([se, empty, empty, empty]),
+        ];
+
+        let quarter = 1 << (root.level - 1);
+        self.root = self.join(children);
+        self.origin = (self.origin.0 - quarter, self.origin.1 - quarter);
+    }
+
+    fn set_in(&mut self, node: NodeId, x: i64, y: i64, alive: bool) -> NodeId {
+        let level = self.node(node).level;
+
+        if level == 0 {
+            return if alive { ALIVE } else { DEAD };
+        }
+
+        let half = 1 << (level - 1);
+        let quadrant = (y >= half) as usize * 2 + (x >= half) as usize;
+        let mut children = self.node(node).children;
+        children[quadrant] = self.set_in(children[quadrant], x % half, y % half, alive);
+        self.join(children)
+    }
+
+    /// Whether every live cell is in the middle half of the root, far enough from its edges.
+    fn is_padded(&self) -> bool {
+        let root = self.node(self.root);
+        let [nw, ne, sw, se] = root.children;
+        let inner = [
+            self.node(nw).children[3],
+            self.node(ne).children[2],
+            self.node(sw).children[1],
+            self.node(se).children[0],
+        ];
+
+        inner
+            .iter()
+            .map(|&id| self.node(id).population)
+            .sum::<u64>()
+            == root.population
+    }
+
+    fn step_power_of_two(&mut self, j: u8) {
+        while self.node(self.root).level < j + 2 || !self.is_padded() {
+            self.expand();
+        }
+
+        // One more, so that what grows out of the middle half in 2^j generations stays within
+        // the centre the successor covers.
+        self.expand();
+
+        let quarter = 1 << (self.node(self.root).level - 2);
+        self.root = self.successor(self.root, j);
+        self.origin = (self.origin.0 + quarter, self.origin.1 + quarter);
+        self.generation += 1 << j;
+    }
+
+    /// The centre half of `node`, `2^j` generations later, for `j` up to `level - 2`.
+    fn successor(&mut self, node: NodeId, j: u8) -> NodeId {
+        let Node {
+            level, population, ..
+        } = *self.node(node);
+        let j = j.min(level - 2);
+
+        // Without B0, nothing comes out of nothing.
+        if population == 0 {
+            return self.empty_node(level - 1);
+        }
+
+        if let Some(&result) = self.successors.get(&(node, j)) {
+            return result;
+        }
+
+        let result = if level == 2 {
+            self.step_4x4(node)
+        } else {
+            let [nw, ne, sw, se] = self.node(node).children;
+            let [nw_nw, nw_ne, nw_sw, nw_se] = self.node(nw).children;
+            let [ne_nw, ne_ne, ne_sw, ne_se] = self.node(ne).children;
+            let [sw_nw, sw_ne, sw_sw, sw_se] = self.node(sw).children;
+            let [se_nw, se_ne, se_sw, se_se] = self.node(se).children;
+
+            // The nine overlapping blocks of half the size, stepped.
+            let blocks = [
+                [nw_nw, nw_ne, nw_sw, nw_se],
+                [nw_ne, ne_nw, nw_se, ne_sw],
+                [ne_nw, ne_ne, ne_sw, ne_se],
+                [nw_sw, nw_se, sw_nw, sw_ne],
+                [nw_se, ne_sw, sw_ne, se_nw],
+                [ne_sw, ne_se, se_nw, se_ne],
+                [sw_nw, sw_ne, sw_sw, sw_se],
+                [sw_ne, se_nw, sw_se, se_sw],
+                [se_nw, se_ne, se_sw, se_se],
+            ];
+            let mut stepped = [DEAD; 9];
+            for (idx, &children) in blocks.iter().enumerate() {
+                let block = self.join(children);
+                stepped[idx] = self.successor(block, j);
+            }
+
+            let [c1, c2, c3, c4, c5, c6, c7, c8, c9] = stepped;
+            let quadrants = [
+                [c1, c2, c4, c5],
+                [c2, c3, c5, c6],
+                [c4, c5, c7, c8],
+                [c5, c6, c8, c9],
+            ];
+            let mut result = [DEAD; 4];
+
+            if j < level - 2 {
+                // Already far enough: only keep the centres.
+                for (idx, &[a, b, c, d]) in quadrants.iter().enumerate() {
+                    let centre = [
+                        self.node(a).children[3],
+                        self.node(b).children[2],
+                        self.node(c).children[1],
+                        self.node(d).children[0],
+                    ];
+                    result[idx] = self.join(centre);
+                }
+            } else {
+                // Halfway there: step again.
+                for (idx, &children) in quadrants.iter().enumerate() {
+                    let block = self.join(children);
+                    result[idx] = self.successor(block, j);
+                }
+            }
+
+            self.join(result)
+        };
+
+        self.successors.insert((node, j), result);
+        result
+    }
+
+    /// The centre 2x2 cells of a 4x4 node, one generation later.
+    fn step_4x4(&mut self, node: NodeId) -> NodeId {
+        let mut cells = [[false; 4]; 4];
+
+        for (quadrant, &child) in self.node(node).children.iter().enumerate() {
+            for (idx, &leaf) in self.node(child).children.iter().enumerate() {
+                let x = quadrant % 2 * 2 + idx % 2;
+                let y = quadrant / 2 * 2 + idx / 2;
+                cells[y][x] = leaf == ALIVE;
+            }
+        }
+
+        let mut centre = [DEAD; 4];
+
+        for (idx, next) in centre.iter_mut().enumerate() {
+            let (x, y) = (1 + idx % 2, 1 + idx / 2);
+            let neighbours = self
+                .neighbourhood
+                .offsets()
+                .iter()
+                .filter(|&&(dx, dy)| cells[(y as isize + dy) as usize][(x as isize + dx) as usize])
+                .count();
+
+            if self.rule.next(cells[y][x], neighbours) {
+                *next = ALIVE;
+            }
+        }
+
+        self.join(centre)
+    }
+
+    fn fill(&self, board: &mut Board, window: (i64, i64, i64, i64), id: NodeId, at: (i64, i64)) {
+        let node = self.node(id);
+        let size = 1 << node.level;
+        let (left, top, right, bottom) = window;
+
+        if node.population == 0
+            || at.0 >= right
+            || at.1 >= bottom
+            || at.0 + size <= left
+            || at.1 + size <= top
+        {
+            return;
+        }
+
+        if node.level == 0 {
+            board.set((at.0 - left) as usize, (at.1 - top) as usize, true);
+            return;
+        }
+
+        let half = size / 2;
+        for (quadrant, &child) in node.children.iter().enumerate() {
+            let x = at.0 + (quadrant % 2) as i64 * half;
+            let y = at.1 + (quadrant / 2) as i64 * half;
+            self.fill(board, window, child, (x, y));
+        }
+    }
+}
+
+/// Refuse rules with B0, which `Hashlife` cannot follow.
+fn check(rule: LifeRule) -> Result<(), String> {
+    if rule.fills_empty_space() {
+        Err(format!("hashlife cannot run {}, which has B0", rule))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::automata::life::Neighbourhood;
+    use crate::automata::rle;
+    use crate::automata::Boundary;
+    use rand::{Rng, SeedableRng};
+
+    /// Large enough that the test patterns never reach the edges of the naive board.
+    const SIDE: usize = 96;
+
+    fn conway() -> LifeRule {
+        "B3/S23".parse().unwrap()
+    }
+
+    /// The same pattern on a board, centred, and in a universe with the same coordinates.
+    fn both(pattern: &str, rule: LifeRule, neighbourhood: Neighbourhood) -> (Board, Hashlife) {
+        let pattern = rle::parse(pattern).unwrap();
+        let mut board = Board::new(SIDE, SIDE);
+        let (x, y) = pattern.centered_on(&board);
+        pattern.place(&mut board, x, y);
+
+        let mut universe = Hashlife::new(rule, neighbourhood).unwrap();
+        for &(cell_x, cell_y) in &pattern.cells {
+            universe.set((x + cell_x) as i64, (y + cell_y) as i64, true);
+        }
+
+        (board, universe)
+    }
+
+    fn assert_same(board: &Board, universe: &Hashlife, generation: u64) {
+        let window = universe.window(0, 0, SIDE, SIDE);
+        assert_eq!(window.cells(), board.cells(), "generation {}", generation);
+        assert_eq!(
+            universe.population(),
+            board.cells().iter().filter(|&&alive| alive).count() as u64,
+            "population at generation {}",
+            generation
+        );
+    }
+
+    /// Step both one generation at a time, then check larger strides against the board.
+    fn compare(pattern: &str, rule: LifeRule, neighbourhood: Neighbourhood, generations: u64) {
+        let (mut board, mut universe) = both(pattern, rule, neighbourhood);
+
+        for generation in 0..generations {
+            assert_same(&board, &universe, generation);
+            board.step(&rule, neighbourhood, Boundary::Dead);
+            universe.step(1);
+        }
+        assert_same(&board, &universe, generations);
+
+        let (_, mut strided) = both(pattern, rule, neighbourhood);
+        strided.step(generations);
+        assert_eq!(strided.generation(), generations);
+        assert_same(&board, &strided, generations);
+    }
+
+    #[test]
+    fn glider() {
+        compare(
+            "x = 3, y = 3\nbob$2bo$3o!",
+            conway(),
+            Neighbourhood::Moore,
+            40,
+        );
+    }
+
+    #[test]
+    fn r_pentomino() {
+        compare(
+            "x = 3, y = 3\nb2o$2o$bo!",
+            conway(),
+            Neighbourhood::Moore,
+            60,
+        );
+    }
+
+    #[test]
+    fn blinker_and_block() {
+        compare(
+            "x = 7, y = 2\n3o2b2o$5b2o!",
+            conway(),
+            Neighbourhood::Moore,
+            9,
+        );
+    }
+
+    #[test]
+    fn highlife_replicator() {
+        compare(
+            "x = 5, y = 5\n2b3o$bo2bo$o3bo$o2bo$3o!",
+            "B36/S23".parse().unwrap(),
+            Neighbourhood::Moore,
+            30,
+        );
+    }
+
+    /// A random 16 by 16 pattern.
+    fn soup(seed: u64) -> String {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut rows = Vec::new();
+
+        for _ in 0..16 {
+            let row: String = (0..16)
+                .map(|_| if rng.gen_bool(0.4) { 'o' } else { 'b' })
+                .collect();
+            rows.push(row);
+        }
+
+        format!("x = 16, y = 16\n{}!", rows.join("$"))
+    }
+
+    #[test]
+    fn random_soup() {
+        compare(&soup(20), conway(), Neighbourhood::Moore, 25);
+    }
+
+    #[test]
+    fn von_neumann_soup() {
+        let rule = "B13/S012".parse().unwrap();
+        compare(&soup(21), rule, Neighbourhood::VonNeumann, 25);
+    }
+
+    #[test]
+    fn b0_is_refused() {
+        let b0: LifeRule = "B03/S23".parse().unwrap();
+        assert!(Hashlife::new(b0, Neighbourhood::Moore).is_err());
+
+        let mut universe = Hashlife::new(conway(), Neighbourhood::Moore).unwrap();
+        assert!(universe.set_rule(b0).is_err());
+        assert_eq!(universe.rule(), conway());
+    }
+
+    /// Dropping the unused nodes along the way changes nothing to the outcome.
+    #[test]
+    fn garbage_collection() {
+        let (mut board, mut universe) = both(&soup(22), conway(), Neighbourhood::Moore);
+        universe.node_limit = 300;
+
+        for generation in 0..40 {
+            assert_same(&board, &universe, generation);
+            board.step(&conway(), Neighbourhood::Moore, Boundary::Dead);
+            universe.step(1);
+            assert!(universe.nodes.len() <= 2 * universe.node_limit);
+        }
+        assert_same(&board, &universe, 40);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most")]
+    fn oversized_step() {
+        let mut universe = Hashlife::new(conway(), Neighbourhood::Moore).unwrap();
+        universe.step(Hashlife::MAX_STEP + 1);
+    }
+
+    #[test]
+    fn set_and_get_anywhere() {
+        let mut universe = Hashlife::new(conway(), Neighbourhood::Moore).unwrap();
+        let cells = [(0, 0), (-5, 3), (1000, -1000), (-70_000, 12)];
+
+        for &(x, y) in &cells {
+            universe.set(x, y, true);
+        }
+        for &(x, y) in &cells {
+            assert!(universe.get(x, y));
+            assert!(!universe.get(x + 1, y));
+        }
+        assert_eq!(universe.population(), cells.len() as u64);
+
+        universe.set(1000, -1000, false);
+        assert!(!universe.get(1000, -1000));
+        assert_eq!(universe.population(), cells.len() as u64 - 1);
+    }
+
+    /// A glider moves one cell diagonally every four generations, for as long as it takes.
+    #[test]
+    fn glider_after_a_million_generations() {
+        let glider = rle::parse("x = 3, y = 3\nbob$2bo$3o!").unwrap();
+        let mut universe = Hashlife::new(conway(), Neighbourhood::Moore).unwrap();
+        for &(x, y) in &glider.cells {
+            universe.set(x as i64, y as i64, true);
+        }
+
+        let generations = 1 << 20;
+        universe.step(generations);
+
+        let offset = (generations / 4) as i64;
+        assert_eq!(universe.population(), 5);
+        for &(x, y) in &glider.cells {
+            assert!(universe.get(x as i64 + offset, y as i64 + offset));
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! Hexagonal grids: axial coordinates, the six directions, and the turns of ants walking them.
+//!
+//! Hexagons are pointy-topped and stored in a `Grid` row by row, odd rows shifted half a cell to
+//! the right. Rows count upwards, like the rows of the square grids the sketches draw.
+
+use super::grid::{Grid, Step, Topology};
+use super::turmite::Turning;
+
+/// A hexagon in axial coordinates: `q` grows to the right along a row and `r` upwards, `q` also
+/// shifting half a cell to the right with every row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+impl Hex {
+    /// The hexagon in column `x` and row `y` of a grid.
+    pub fn from_offset(x: isize, y: isize) -> Self {
+        Hex {
+            q: x - (y - (y & 1)) / 2,
+            r: y,
+        }
+    }
+
+    /// The column and row of the hexagon in a grid.
+    pub fn to_offset(self) -> (isize, isize) {
+        (self.q + (self.r - (self.r & 1)) / 2, self.r)
+    }
+
+    pub fn neighbour(self, direction: HexDirection) -> Self {
+        let (dq, dr) = direction.offset();
+        Hex {
+            q: self.q + dq,
+            r: self.r + dr,
+        }
+    }
+}
+
+/// The six neighbours of a hexagon, clockwise from the one on the right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    /// Clockwise, from `East`.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    /// The change of axial coordinates going this way.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            HexDirection::East => (1, 0),
+            HexDirection::SouthEast => (1, -1),
+            HexDirection::SouthWest => (0, -1),
+            HexDirection::West => (-1, 0),
+            HexDirection::NorthWest => (-1, 1),
+            HexDirection::NorthEast => (0, 1),
+        }
+    }
+
+    pub fn turn(self, turn: HexTurn) -> Self {
+        let current = HexDirection::ALL
+            .iter()
+            .position(|&direction| direction == self)
+            .unwrap();
+
+        HexDirection::ALL[(current + usize::from(turn.clockwise())) % 6]
+    }
+}
+
+/// Which way an ant turns on a hexagonal grid, by sixths of a full turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexTurn {
+    /// Keep going straight, N in rule strings.
+    None,
+    /// 60° to the right, R1.
+    Right1,
+    /// 120° to the right, R2.
+    Right2,
+    /// Head back where it came from, U.
+    UTurn,
+    /// 120° to the left, L2.
+    Left2,
+    /// 60° to the left, L1.
+    Left1,
+}
+
+impl Turning for HexTurn {
+    const DIRECTIONS: u8 = 6;
+
+    const NAMES: &'static [(&'static str, Self)] = &[
+        ("N", HexTurn::None),
+        ("R1", HexTurn::Right1),
+        ("R2", HexTurn::Right2),
+        ("U", HexTurn::UTurn),
+        ("L2", HexTurn::Left2),
+        ("L1", HexTurn::Left1),
+    ];
+
+    const PRESETS: &'static [(&'static str, &'static str)] = &[
+        ("two-colour", "L2R2"),
+        ("three-colour", "L1R1R2"),
+        ("seven-colour", "L1L2NUL2L1R2"),
+        ("seven-colour-mirrored", "R1R2NUR2R1L2"),
+    ];
+
+    fn clockwise(self) -> u8 {
+        match self {
+            HexTurn::None => 0,
+            HexTurn::Right1 => 1,
+            HexTurn::Right2 => 2,
+            HexTurn::UTurn => 3,
+            HexTurn::Left2 => 4,
+            HexTurn::Left1 => 5,
+        }
+    }
+}
+
+/// The hexagon next to the one in column `x` and row `y` of `grid`, through the edges as
+/// `topology` connects them. `None` past the edges of a bounded grid.
+///
+/// The wrapping of a torus only lines the hexagons up with an even number of rows. Panics on a
+/// Klein bottle, which `check_topology` rules out.
+pub fn step<T>(
+    grid: &Grid<T>,
+    x: usize,
+    y: usize,
+    direction: HexDirection,
+    topology: Topology,
+) -> Option<Step> {
+    let (columns, rows) = (grid.columns() as isize, grid.rows() as isize);
+    let (x, y) = Hex::from_offset(x as isize, y as isize)
+        .neighbour(direction)
+        .to_offset();
+
+    match topology {
+        Topology::Torus => Some(Step {
+            x: x.rem_euclid(columns) as usize,
+            y: y.rem_euclid(rows) as usize,
+            mirrored: false,
+        }),
+        Topology::Bounded if 0 <= x && x < columns && 0 <= y && y < rows => Some(Step {
+            x: x as usize,
+            y: y as usize,
+            mirrored: false,
+        }),
+        Topology::Bounded => None,
+        Topology::KleinBottle => panic!("hexagonal grids cannot be glued into a Klein bottle"),
+    }
+}
+
+/// Whether hexagons can be glued as `topology` says. Not into a Klein bottle: mirrored, the
+/// shifted rows would not line up.
+pub fn check_topology(topology: Topology) -> Result<(), String> {
+    match topology {
+        Topology::Torus | Topology::Bounded => Ok(()),
+        Topology::KleinBottle => Err("hexagons cannot be glued into a Klein bottle".to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::automata::turmite::TurmiteRule;
+
+    #[test]
+    fn offset_and_axial_agree() {
+        for y in -4..4 {
+            for x in -4..4 {
+                assert_eq!(Hex::from_offset(x, y).to_offset(), (x, y));
+            }
+        }
+    }
+
+    /// Odd rows are shifted right, so the hexagons above and below a cell are not in the same
+    /// columns on even and odd rows.
+    #[test]
+    fn neighbours_in_offset_coordinates() {
+        let around = |x, y| -> Vec<(isize, isize)> {
+            HexDirection::ALL
+                .iter()
+                .map(|&direction| Hex::from_offset(x, y).neighbour(direction).to_offset())
+                .collect()
+        };
+
+        assert_eq!(
+            around(2, 2),
+            vec![(3, 2), (2, 1), (1, 1), (1, 2), (1, 3), (2, 3)]
+        );
+        assert_eq!(
+            around(2, 1),
+            vec![(3, 1), (3, 0), (2, 0), (1, 1), (2, 2), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn turns_go_around_clockwise() {
+        let east = HexDirection::East;
+        assert_eq!(east.turn(HexTurn::Right1), HexDirection::SouthEast);
+        assert_eq!(east.turn(HexTurn::Left1), HexDirection::NorthEast);
+        assert_eq!(east.turn(HexTurn::Left2), HexDirection::NorthWest);
+        assert_eq!(east.turn(HexTurn::UTurn), HexDirection::West);
+
+        for &turn in &[HexTurn::Right1, HexTurn::Right2, HexTurn::UTurn] {
+            assert_eq!(east.turn(turn).turn(turn.inverse()), east);
+        }
+    }
+
+    /// On a torus with an even number of rows, and inside a bounded grid, going one way then the
+    /// opposite way comes back, edges included.
+    #[test]
+    fn steps_are_undone_by_the_opposite_step() {
+        let grid = Grid::new(5, 4, ());
+
+        for &topology in &[Topology::Torus, Topology::Bounded] {
+            for idx in 0..grid.len() {
+                let (x, y) = grid.coords(idx);
+
+                for &direction in &HexDirection::ALL {
+                    let there = match step(&grid, x, y, direction, topology) {
+                        Some(there) => there,
+                        None => continue,
+                    };
+                    let back = step(
+                        &grid,
+                        there.x,
+                        there.y,
+                        direction.turn(HexTurn::UTurn),
+                        topology,
+                    );
+                    assert_eq!(back.map(|back| (back.x, back.y)), Some((x, y)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bounded_stops_at_the_edges() {
+        let grid = Grid::new(5, 4, ());
+        assert_eq!(
+            step(&grid, 0, 0, HexDirection::West, Topology::Bounded),
+            None
+        );
+        assert_eq!(
+            step(&grid, 0, 0, HexDirection::SouthEast, Topology::Bounded),
+            None
+        );
+        assert_eq!(
+            step(&grid, 0, 2, HexDirection::NorthWest, Topology::Bounded),
+            None
+        );
+        assert_eq!(
+            step(&grid, 4, 1, HexDirection::NorthEast, Topology::Bounded),
+            None
+        );
+        assert_eq!(
+            step(&grid, 4, 1, HexDirection::NorthWest, Topology::Bounded),
+            Some(Step {
+                x: 4,
+                y: 2,
+                mirrored: false
+            })
+        );
+    }
+
+    #[test]
+    fn hex_rule_strings() {
+        let rule: TurmiteRule<HexTurn> = "l1L2NUL2L1R2".parse().unwrap();
+        assert_eq!(rule.colours(), 7);
+        assert_eq!(rule.transition(0, 0).turn, HexTurn::Left1);
+        assert_eq!(rule.transition(0, 6).turn, HexTurn::Right2);
+        assert_eq!(rule.to_string(), "L1L2NUL2L1R2");
+
+        assert!("L1R".parse::<TurmiteRule<HexTurn>>().is_err());
+        assert!("L3".parse::<TurmiteRule<HexTurn>>().is_err());
+    }
+}
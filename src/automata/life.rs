@@ -0,0 +1,349 @@
+//! Life-like automata: two-dimensional, two states, each cell born or surviving depending on how
+//! many of its neighbours are alive.
+
+use super::boundary::{Boundary, Lookup};
+use std::fmt;
+use std::str::FromStr;
+
+/// Which cells count as neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The eight surrounding cells.
+    Moore,
+    /// The four orthogonally adjacent cells.
+    VonNeumann,
+}
+
+const MOORE: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+const VON_NEUMANN: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+impl Neighbourhood {
+    /// The offsets of the neighbours from the cell.
+    pub fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbourhood::Moore => &MOORE,
+            Neighbourhood::VonNeumann => &VON_NEUMANN,
+        }
+    }
+}
+
+impl FromStr for Neighbourhood {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "moore" => Ok(Neighbourhood::Moore),
+            "von-neumann" => Ok(Neighbourhood::VonNeumann),
+            other => Err(format!("expected moore or von-neumann, got {:?}", other)),
+        }
+    }
+}
+
+/// A rule in B/S notation: the live neighbour counts a dead cell is born with, and those a live
+/// cell survives with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifeRule {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+impl LifeRule {
+    /// The named rules, as `(name, rule string)`. The names parse as well as the rule strings.
+    pub const PRESETS: &'static [(&'static str, &'static str)] = &[
+        ("conway", "B3/S23"),
+        ("highlife", "B36/S23"),
+        ("seeds", "B2/S"),
+        ("day-and-night", "B3678/S34678"),
+    ];
+
+    /// Whether dead cells without live neighbours are born, so that empty space fills up.
+    pub fn fills_empty_space(&self) -> bool {
+        self.birth[0]
+    }
+
+    /// The next state of a cell with `neighbours` live neighbours.
+    pub fn next(&self, alive: bool, neighbours: usize) -> bool {
+        if alive {
+            self.survival[neighbours]
+        } else {
+            self.birth[neighbours]
+        }
+    }
+}
+
+impl FromStr for LifeRule {
+    type Err = String;
+
+    /// Parse a preset name, B/S notation such as `B3/S23`, or the older S/B notation such as
+    /// `23/3`, survival counts first.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(&(_, rule)) = LifeRule::PRESETS.iter().find(|(name, _)| *name == s) {
+            return rule.parse();
+        }
+
+        let mut rule = LifeRule {
+            birth: [false; 9],
+            survival: [false; 9],
+        };
+
+        let is_counts = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if let Some((survival, birth)) = s.split_once('/') {
+            if is_counts(survival) && is_counts(birth) {
+                parse_counts(survival, &mut rule.survival, s)?;
+                parse_counts(birth, &mut rule.birth, s)?;
+                return Ok(rule);
+            }
+        }
+
+        for part in s.split('/') {
+            let mut chars = part.chars();
+            let counts = match chars.next() {
+                Some('B') | Some('b') => &mut rule.birth,
+                Some('S') | Some('s') => &mut rule.survival,
+                _ => return Err(format!("expected a rule like B3/S23, got {:?}", s)),
+            };
+
+            parse_counts(chars.as_str(), counts, s)?;
+        }
+
+        Ok(rule)
+    }
+}
+
+/// Set the neighbour counts listed in `digits`, a part of `rule`.
+fn parse_counts(digits: &str, counts: &mut [bool; 9], rule: &str) -> Result<(), String> {
+    for digit in digits.chars() {
+        match digit.to_digit(10) {
+            Some(count) if count <= 8 => counts[count as usize] = true,
+            _ => return Err(format!("invalid neighbour count {:?} in {:?}", digit, rule)),
+        }
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for LifeRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = |counts: &[bool; 9]| -> String {
+            (0..9)
+                .filter(|&count| counts[count])
+                .map(|count| count.to_string())
+                .collect()
+        };
+
+        write!(f, "B{}/S{}", counts(&self.birth), counts(&self.survival))
+    }
+}
+
+/// A grid of cells, row by row from the top left, and the buffer its next generation is computed
+/// into.
+#[derive(Debug, Clone, Default)]
+pub struct Board {
+    columns: usize,
+    rows: usize,
+    cells: Vec<bool>,
+    next: Vec<bool>,
+}
+
+impl Board {
+    /// A board of dead cells.
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Board {
+            columns,
+            rows,
+            cells: vec![false; columns * rows],
+            next: vec![false; columns * rows],
+        }
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cells(&self) -> &[bool] {
+        &self.cells
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.cells[y * self.columns + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, state: bool) {
+        self.cells[y * self.columns + x] = state;
+    }
+
+    /// The state of the cell at `(x, y)`, which may be past the edges.
+    pub fn state(&self, x: isize, y: isize, boundary: Boundary) -> bool {
+        match (
+            boundary.resolve(x, self.columns),
+            boundary.resolve(y, self.rows),
+        ) {
+            (Lookup::Cell(x), Lookup::Cell(y)) => self.get(x, y),
+            (Lookup::Fixed(state), _) | (_, Lookup::Fixed(state)) => state,
+        }
+    }
+
+    /// Replace the board with its next generation, every cell computed from the current one.
+    pub fn step(&mut self, rule: &LifeRule, neighbourhood: Neighbourhood, boundary: Boundary) {
+        for y in 0..self.rows {
+            for x in 0..self.columns {
+                let (x, y) = (x as isize, y as isize);
+                let neighbours = neighbourhood
+                    .offsets()
+                    .iter()
+                    .filter(|&&(dx, dy)| self.state(x + dx, y + dy, boundary))
+                    .count();
+
+                let idx = y as usize * self.columns + x as usize;
+                self.next[idx] = rule.next(self.cells[idx], neighbours);
+            }
+        }
+
+        std::mem::swap(&mut self.cells, &mut self.next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A board from rows of `.` and `#`.
+    fn board(rows: &[&str]) -> Board {
+        let mut board = Board::new(rows[0].len(), rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.chars().enumerate() {
+                board.set(x, y, cell == '#');
+            }
+        }
+        board
+    }
+
+    fn render(board: &Board) -> Vec<String> {
+        board
+            .cells()
+            .chunks(board.columns())
+            .map(|row| {
+                row.iter()
+                    .map(|&alive| if alive { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn conway() -> LifeRule {
+        "B3/S23".parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let rule: LifeRule = "B36/S23".parse().unwrap();
+        assert!(rule.next(false, 3) && rule.next(false, 6));
+        assert!(!rule.next(false, 2) && !rule.next(false, 4));
+        assert!(rule.next(true, 2) && rule.next(true, 3));
+        assert!(!rule.next(true, 1) && !rule.next(true, 6));
+        assert!(!rule.fills_empty_space());
+
+        assert_eq!("b3/s23".parse(), Ok(conway()));
+        assert_eq!("S23/B3".parse(), Ok(conway()));
+        assert_eq!("23/3".parse(), Ok(conway()));
+        assert!("B0/S8".parse::<LifeRule>().unwrap().fills_empty_space());
+
+        let seeds: LifeRule = "B2/S".parse().unwrap();
+        assert!((0..9).all(|count| !seeds.next(true, count)));
+    }
+
+    #[test]
+    fn display() {
+        for &rule in &["B3/S23", "B36/S23", "B2/S", "B3678/S34678", "B/S012345678"] {
+            assert_eq!(rule.parse::<LifeRule>().unwrap().to_string(), rule);
+        }
+        assert_eq!("23/3".parse::<LifeRule>().unwrap().to_string(), "B3/S23");
+    }
+
+    #[test]
+    fn presets() {
+        assert_eq!("conway".parse(), Ok(conway()));
+        assert_eq!("highlife".parse(), "B36/S23".parse::<LifeRule>());
+
+        for &(name, rule) in LifeRule::PRESETS {
+            assert_eq!(name.parse::<LifeRule>(), rule.parse::<LifeRule>());
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        for &rule in &[
+            "", "conways", "B9/S23", "B3/S2x", "X3/S23", "B3/23", "3/S23", "2/3/4",
+        ] {
+            assert!(rule.parse::<LifeRule>().is_err(), "{:?}", rule);
+        }
+    }
+
+    #[test]
+    fn blinker() {
+        let mut blinker = board(&[".....", ".....", ".###.", ".....", "....."]);
+        let vertical = [".....", "..#..", "..#..", "..#..", "....."];
+        let horizontal = render(&blinker);
+
+        blinker.step(&conway(), Neighbourhood::Moore, Boundary::Dead);
+        assert_eq!(render(&blinker), vertical);
+        blinker.step(&conway(), Neighbourhood::Moore, Boundary::Dead);
+        assert_eq!(render(&blinker), horizontal);
+    }
+
+    #[test]
+    fn glider() {
+        let mut glider = board(&[".#....", "..#...", "###...", "......", "......", "......"]);
+
+        for _ in 0..4 {
+            glider.step(&conway(), Neighbourhood::Moore, Boundary::Dead);
+        }
+
+        assert_eq!(
+            render(&glider),
+            ["......", "..#...", "...#..", ".###..", "......", "......"]
+        );
+    }
+
+    /// Around the edges of a periodic board, the glider comes back where it started.
+    #[test]
+    fn glider_on_a_torus() {
+        let start = board(&[".#....", "..#...", "###...", "......", "......", "......"]);
+        let mut glider = start.clone();
+
+        for _ in 0..24 {
+            glider.step(&conway(), Neighbourhood::Moore, Boundary::Periodic);
+        }
+
+        assert_eq!(render(&glider), render(&start));
+    }
+
+    #[test]
+    fn von_neumann() {
+        // Born with one orthogonal neighbour, never surviving: a growing diamond outline.
+        let rule: LifeRule = "B1/S".parse().unwrap();
+        let mut cell = board(&[".....", ".....", "..#..", ".....", "....."]);
+
+        cell.step(&rule, Neighbourhood::VonNeumann, Boundary::Dead);
+        assert_eq!(render(&cell), [".....", "..#..", ".#.#.", "..#..", "....."]);
+
+        // The same under Moore counts the diagonals too.
+        let mut cell = board(&[".....", ".....", "..#..", ".....", "....."]);
+        cell.step(&rule, Neighbourhood::Moore, Boundary::Dead);
+        assert_eq!(render(&cell), [".....", ".###.", ".#.#.", ".###.", "....."]);
+    }
+}
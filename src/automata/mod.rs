@@ -0,0 +1,14 @@
+//! Cellular automata engines, independent of how the sketches draw them.
+
+pub mod bitrow;
+pub mod boundary;
+pub mod elementary;
+pub mod grid;
+pub mod hashlife;
+pub mod hex;
+pub mod life;
+pub mod multistate;
+pub mod rle;
+pub mod turmite;
+
+pub use self::boundary::Boundary;
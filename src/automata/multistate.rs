@@ -0,0 +1,293 @@
+//! Automata with more than two states per cell: cyclic automata and excitable media.
+
+use super::boundary::Boundary;
+use super::grid::Grid;
+use super::life::Neighbourhood;
+use std::fmt;
+
+/// The state of a cell past the edges when the boundary fixes it dead.
+pub const REST: u8 = 0;
+
+/// The state of a cell past the edges when the boundary fixes it alive.
+pub const EXCITED: u8 = 1;
+
+/// How a cell's state follows from its own and its neighbours'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiRule {
+    /// A cell in state `k` moves on to `k + 1`, modulo `states`, when at least `threshold` of its
+    /// neighbours are already there. Spirals out of noise.
+    Cyclic { states: u8, threshold: usize },
+    /// Excitable medium: a resting cell (`REST`) gets excited (`EXCITED`) when at least
+    /// `threshold` neighbours are, then goes through the refractory states `2..states` back to
+    /// rest.
+    GreenbergHastings { states: u8, threshold: usize },
+    /// Off cells (0) fire (1) with exactly two firing neighbours, firing cells are dying (2) the
+    /// next generation, dying ones are off the one after.
+    BriansBrain,
+}
+
+impl MultiRule {
+    /// The rule names `parse` accepts.
+    pub const NAMES: &'static [&'static str] = &["cyclic", "greenberg-hastings", "brians-brain"];
+
+    /// The rule called `name`, with `states` and `threshold` where it takes them, `states` being
+    /// at least 2.
+    pub fn parse(name: &str, states: u8, threshold: usize) -> Result<Self, String> {
+        if states < 2 && name != "brians-brain" {
+            return Err(format!("{} needs at least 2 states, got {}", name, states));
+        }
+
+        match name {
+            "cyclic" => Ok(MultiRule::Cyclic { states, threshold }),
+            "greenberg-hastings" => Ok(MultiRule::GreenbergHastings { states, threshold }),
+            "brians-brain" => Ok(MultiRule::BriansBrain),
+            other => Err(format!(
+                "expected one of {}, got {:?}",
+                MultiRule::NAMES.join(", "),
+                other
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MultiRule::Cyclic { .. } => "cyclic",
+            MultiRule::GreenbergHastings { .. } => "greenberg-hastings",
+            MultiRule::BriansBrain => "brians-brain",
+        }
+    }
+
+    /// The number of states, numbered from 0.
+    pub fn states(self) -> u8 {
+        match self {
+            MultiRule::Cyclic { states, .. } | MultiRule::GreenbergHastings { states, .. } => {
+                states
+            }
+            MultiRule::BriansBrain => 3,
+        }
+    }
+
+    /// The next state of a cell in `state`, with neighbours in the states `neighbours`.
+    pub fn next<I>(self, state: u8, neighbours: I) -> u8
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let states = self.states();
+
+        match self {
+            MultiRule::Cyclic { threshold, .. } => {
+                let successor = (state + 1) % states;
+
+                if count(neighbours, successor) >= threshold {
+                    successor
+                } else {
+                    state
+                }
+            }
+            MultiRule::GreenbergHastings { threshold, .. } => match state {
+                REST if count(neighbours, EXCITED) >= threshold => EXCITED,
+                REST => REST,
+                state => (state + 1) % states,
+            },
+            MultiRule::BriansBrain => match state {
+                0 if count(neighbours, 1) == 2 => 1,
+                0 => 0,
+                1 => 2,
+                _ => 0,
+            },
+        }
+    }
+}
+
+impl fmt::Display for MultiRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MultiRule::Cyclic { states, threshold }
+            | MultiRule::GreenbergHastings { states, threshold } => write!(
+                f,
+                "{} ({} states, threshold {})",
+                self.name(),
+                states,
+                threshold
+            ),
+            MultiRule::BriansBrain => f.write_str(self.name()),
+        }
+    }
+}
+
+fn count<I: IntoIterator<Item = u8>>(neighbours: I, state: u8) -> usize {
+    neighbours
+        .into_iter()
+        .filter(|&neighbour| neighbour == state)
+        .count()
+}
+
+/// A grid of multi-state cells, and the buffer its next generation is computed into.
+#[derive(Debug, Clone)]
+pub struct StateGrid {
+    cells: Grid<u8>,
+    next: Grid<u8>,
+}
+
+impl StateGrid {
+    /// A grid of cells in state 0.
+    pub fn new(columns: usize, rows: usize) -> Self {
+        StateGrid {
+            cells: Grid::new(columns, rows, 0),
+            next: Grid::new(columns, rows, 0),
+        }
+    }
+
+    pub fn columns(&self) -> usize {
+        self.cells.columns()
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.rows()
+    }
+
+    /// The states, row by row from the top left.
+    pub fn cells(&self) -> &[u8] {
+        self.cells.cells()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        *self.cells.get(x, y)
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, state: u8) {
+        *self.cells.get_mut(x, y) = state;
+    }
+
+    /// The state of the cell at `(x, y)`, which may be past the edges: `EXCITED` where the
+    /// boundary is alive, `REST` where it is dead.
+    pub fn state(&self, x: isize, y: isize, boundary: Boundary) -> u8 {
+        match self.cells.resolve(x, y, boundary) {
+            Ok(&state) => state,
+            Err(true) => EXCITED,
+            Err(false) => REST,
+        }
+    }
+
+    /// Replace the grid with its next generation, every cell computed from the current one.
+    pub fn step(&mut self, rule: MultiRule, neighbourhood: Neighbourhood, boundary: Boundary) {
+        for idx in 0..self.cells.len() {
+            let (x, y) = self.cells.coords(idx);
+            let (x, y) = (x as isize, y as isize);
+            let neighbours = neighbourhood
+                .offsets()
+                .iter()
+                .map(|&(dx, dy)| self.state(x + dx, y + dy, boundary));
+
+            let next = rule.next(self.cells.cells()[idx], neighbours);
+            *self.next.get_mut(x as usize, y as usize) = next;
+        }
+
+        std::mem::swap(&mut self.cells, &mut self.next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A grid from rows of states, one digit per cell.
+    fn grid(rows: &[&str]) -> StateGrid {
+        let mut grid = StateGrid::new(rows[0].len(), rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, state) in row.chars().enumerate() {
+                grid.set(x, y, state.to_digit(10).unwrap() as u8);
+            }
+        }
+        grid
+    }
+
+    fn render(grid: &StateGrid) -> Vec<String> {
+        grid.cells()
+            .chunks(grid.columns())
+            .map(|row| row.iter().map(|state| state.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            MultiRule::parse("cyclic", 14, 1),
+            Ok(MultiRule::Cyclic {
+                states: 14,
+                threshold: 1
+            })
+        );
+        assert_eq!(
+            MultiRule::parse("brians-brain", 0, 0),
+            Ok(MultiRule::BriansBrain)
+        );
+        assert_eq!(MultiRule::parse("brians-brain", 9, 1).unwrap().states(), 3);
+
+        assert!(MultiRule::parse("cyclic", 1, 1).is_err());
+        assert!(MultiRule::parse("cyclic", 0, 1).is_err());
+        assert!(MultiRule::parse("greenberg-hastings", 1, 1).is_err());
+        assert!(MultiRule::parse("life", 3, 1).is_err());
+
+        for &name in MultiRule::NAMES {
+            assert_eq!(MultiRule::parse(name, 3, 1).unwrap().name(), name);
+        }
+    }
+
+    #[test]
+    fn cyclic() {
+        let rule = MultiRule::parse("cyclic", 3, 2).unwrap();
+
+        // Moves on with two neighbours in the next state, wrapping around after the last one.
+        assert_eq!(rule.next(0, vec![1, 1, 0, 2]), 1);
+        assert_eq!(rule.next(0, vec![1, 0, 0, 2]), 0);
+        assert_eq!(rule.next(2, vec![0, 0, 1, 1]), 0);
+        assert_eq!(rule.next(2, vec![1, 1, 1, 1]), 2);
+
+        let mut grid = grid(&["000", "010", "000"]);
+        let rule = MultiRule::parse("cyclic", 3, 1).unwrap();
+        grid.step(rule, Neighbourhood::VonNeumann, Boundary::Dead);
+        assert_eq!(render(&grid), ["010", "111", "010"]);
+    }
+
+    #[test]
+    fn greenberg_hastings() {
+        let rule = MultiRule::parse("greenberg-hastings", 4, 1).unwrap();
+
+        assert_eq!(rule.next(REST, vec![EXCITED, REST]), EXCITED);
+        assert_eq!(rule.next(REST, vec![2, 3]), REST);
+        // Excited, then refractory whatever the neighbours, then back to rest.
+        assert_eq!(rule.next(EXCITED, vec![EXCITED]), 2);
+        assert_eq!(rule.next(2, vec![EXCITED]), 3);
+        assert_eq!(rule.next(3, vec![EXCITED]), REST);
+
+        // A wave moving away from where it came from.
+        let mut grid = grid(&["00000", "02100", "00000"]);
+        grid.step(rule, Neighbourhood::VonNeumann, Boundary::Dead);
+        assert_eq!(render(&grid), ["00100", "03210", "00100"]);
+    }
+
+    #[test]
+    fn brians_brain() {
+        let rule = MultiRule::BriansBrain;
+
+        assert_eq!(rule.next(0, vec![1, 1, 0, 2]), 1);
+        assert_eq!(rule.next(0, vec![1, 1, 1]), 0);
+        assert_eq!(rule.next(1, vec![1, 1]), 2);
+        assert_eq!(rule.next(2, vec![1, 1]), 0);
+
+        // Two firing cells side by side light up the two cells above and the two below them.
+        let mut grid = grid(&["0000", "0110", "0000"]);
+        grid.step(rule, Neighbourhood::Moore, Boundary::Dead);
+        assert_eq!(render(&grid), ["0110", "0220", "0110"]);
+    }
+
+    #[test]
+    fn alive_edges_are_excited() {
+        let rule = MultiRule::parse("greenberg-hastings", 3, 1).unwrap();
+        let mut grid = grid(&["000", "000", "000"]);
+
+        grid.step(rule, Neighbourhood::VonNeumann, Boundary::Alive);
+        assert_eq!(render(&grid), ["111", "101", "111"]);
+    }
+}
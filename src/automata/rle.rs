@@ -0,0 +1,326 @@
+//! The Run Length Encoded format Life patterns are shared in.
+//!
+//! ```text
+//! #N Glider
+//! x = 3, y = 3, rule = B3/S23
+//! bob$2bo$3o!
+//! ```
+//!
+//! `b` is a dead cell, `o` a live one, `$` the end of a row, each optionally preceded by a repeat
+//! count, and `!` the end of the pattern.
+
+use super::life::{Board, LifeRule};
+use failure::{bail, format_err};
+use std::path::Path;
+
+/// The longest line `encode` writes, as the format recommends.
+const LINE_WIDTH: usize = 70;
+
+/// A decoded pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pub width: usize,
+    pub height: usize,
+    /// The rule in the header, if any.
+    pub rule: Option<String>,
+    /// The live cells, as `(x, y)` from the top left.
+    pub cells: Vec<(usize, usize)>,
+}
+
+impl Pattern {
+    pub fn load(path: &Path) -> Result<Self, failure::Error> {
+        parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Set the cells of the pattern on `board`, its top left corner at `(x, y)`. Cells past the
+    /// edges of the board are left out.
+    pub fn place(&self, board: &mut Board, x: usize, y: usize) {
+        for &(cell_x, cell_y) in &self.cells {
+            let (cell_x, cell_y) = (x + cell_x, y + cell_y);
+
+            if cell_x < board.columns() && cell_y < board.rows() {
+                board.set(cell_x, cell_y, true);
+            }
+        }
+    }
+
+    /// The offset that centers the pattern on `board`.
+    pub fn centered_on(&self, board: &Board) -> (usize, usize) {
+        (
+            board.columns().saturating_sub(self.width) / 2,
+            board.rows().saturating_sub(self.height) / 2,
+        )
+    }
+}
+
+/// Decode an RLE pattern. Comment lines are skipped, and any state other than dead counts as
+/// alive.
+pub fn parse(text: &str) -> Result<Pattern, failure::Error> {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    let header = lines.next().ok_or_else(|| format_err!("empty pattern"))?;
+    let mut pattern = Pattern {
+        width: 0,
+        height: 0,
+        rule: None,
+        cells: Vec::new(),
+    };
+
+    // The rule may have commas of its own, as in `B3/S23:T20,10`, so it takes the rest of the
+    // line.
+    let mut rest = header;
+    while !rest.trim().is_empty() {
+        let mut parts = rest.splitn(2, '=');
+        let key = parts.next().unwrap_or_default().trim();
+        let value = parts
+            .next()
+            .ok_or_else(|| format_err!("invalid header field {:?}", rest.trim()))?;
+
+        let (value, next) = match value.find(',') {
+            Some(idx) if key != "rule" => (&value[..idx], &value[idx + 1..]),
+            _ => (value, ""),
+        };
+        let value = value.trim();
+        rest = next;
+
+        match key {
+            "x" => pattern.width = value.parse()?,
+            "y" => pattern.height = value.parse()?,
+            "rule" => pattern.rule = Some(value.to_owned()),
+            other => bail!("unknown header field {:?}", other),
+        }
+    }
+
+    let (mut x, mut y) = (0, 0);
+    let mut count: Option<usize> = None;
+
+    'body: for line in lines {
+        for tag in line.chars() {
+            if let Some(digit) = tag.to_digit(10) {
+                count = count
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|count| count.checked_add(digit as usize));
+
+                if count.is_none() {
+                    bail!("run count too large in {:?}", line);
+                }
+                continue;
+            }
+
+            let run = count.take().unwrap_or(1);
+
+            match tag {
+                '$' => {
+                    x = 0;
+                    y += run;
+
+                    if y > pattern.height {
+                        bail!("more than the {} rows of the header", pattern.height);
+                    }
+                }
+                '!' => break 'body,
+                'b' | '.' | 'o' | 'A'..='X' => {
+                    if run > pattern.width - x || y >= pattern.height {
+                        bail!(
+                            "cells past the {}x{} of the header",
+                            pattern.width,
+                            pattern.height
+                        );
+                    }
+
+                    if tag != 'b' && tag != '.' {
+                        pattern.cells.extend((x..x + run).map(|x| (x, y)));
+                    }
+                    x += run;
+                }
+                tag if tag.is_whitespace() => (),
+                other => bail!("unexpected {:?} in the pattern", other),
+            }
+        }
+    }
+
+    Ok(pattern)
+}
+
+/// Encode the live cells of `board`, with `rule` in the header.
+pub fn encode(board: &Board, rule: Option<&LifeRule>) -> String {
+    let mut out = format!("x = {}, y = {}", board.columns(), board.rows());
+    if let Some(rule) = rule {
+        out.push_str(&format!(", rule = {}", rule));
+    }
+    out.push('\n');
+
+    let mut tokens = Vec::new();
+    // The row the last token is on.
+    let mut last_row = 0;
+
+    for y in 0..board.rows() {
+        let mut runs: Vec<(usize, char)> = Vec::new();
+
+        for x in 0..board.columns() {
+            let tag = if board.get(x, y) { 'o' } else { 'b' };
+
+            match runs.last_mut() {
+                Some((count, last)) if *last == tag => *count += 1,
+                _ => runs.push((1, tag)),
+            }
+        }
+
+        // Trailing dead cells are implied by the end of the row.
+        if let Some(&(_, 'b')) = runs.last() {
+            runs.pop();
+        }
+
+        if runs.is_empty() {
+            continue;
+        }
+
+        if y > last_row {
+            tokens.push(run_token(y - last_row, '$'));
+            last_row = y;
+        }
+
+        tokens.extend(runs.into_iter().map(|(count, tag)| run_token(count, tag)));
+    }
+
+    tokens.push("!".to_owned());
+
+    let mut line = String::new();
+    for token in tokens {
+        if line.len() + token.len() > LINE_WIDTH {
+            out.push_str(&line);
+            out.push('\n');
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    out.push_str(&line);
+    out.push('\n');
+
+    out
+}
+
+fn run_token(count: usize, tag: char) -> String {
+    match count {
+        1 => tag.to_string(),
+        count => format!("{}{}", count, tag),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn header() {
+        let pattern =
+            parse("#N Glider\n#C A comment\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!").unwrap();
+        assert_eq!(pattern.width, 3);
+        assert_eq!(pattern.height, 3);
+        assert_eq!(pattern.rule.as_deref(), Some("B3/S23"));
+
+        let pattern = parse("x = 1, y = 1\no!").unwrap();
+        assert_eq!(pattern.rule, None);
+
+        // Golly's bounded grids, the rule then having a comma.
+        let pattern = parse("x = 3, y = 3, rule = B3/S23:T20,10\nbob$2bo$3o!").unwrap();
+        assert_eq!(pattern.rule.as_deref(), Some("B3/S23:T20,10"));
+        assert_eq!(pattern.cells.len(), 5);
+    }
+
+    #[test]
+    fn glider() {
+        let pattern = parse("x = 3, y = 3\nbob$2bo$3o!").unwrap();
+        assert_eq!(pattern.cells, [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn run_counts() {
+        let pattern = parse("x = 15, y = 1\n3o2b10o!").unwrap();
+        let expected: Vec<_> = (0..3).chain(5..15).map(|x| (x, 0)).collect();
+        assert_eq!(pattern.cells, expected);
+    }
+
+    #[test]
+    fn row_runs() {
+        let pattern = parse("x = 2, y = 6\no$$bo3$o!").unwrap();
+        assert_eq!(pattern.cells, [(0, 0), (1, 2), (0, 5)]);
+    }
+
+    #[test]
+    fn end_of_pattern() {
+        let pattern = parse("x = 2, y = 2\no!\nthis is ignored\nbo").unwrap();
+        assert_eq!(pattern.cells, [(0, 0)]);
+    }
+
+    #[test]
+    fn lines_and_states() {
+        let pattern = parse("x = 5, y = 2\n2o\n  b.A$\nB!").unwrap();
+        assert_eq!(pattern.cells, [(0, 0), (1, 0), (4, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn older_rule_notation() {
+        let pattern = parse("x = 1, y = 1, rule = 23/3\no!").unwrap();
+        let rule: LifeRule = pattern.rule.unwrap().parse().unwrap();
+        assert_eq!(rule, "B3/S23".parse().unwrap());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse("").is_err());
+        assert!(parse("# only a comment").is_err());
+        assert!(parse("x = 3, y\nbo!").is_err());
+        assert!(parse("x = 3, y = 3, z = 1\nbo!").is_err());
+        assert!(parse("x = three, y = 3\nbo!").is_err());
+        assert!(parse("x = 3, y = 3\nbqo!").is_err());
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        assert!(parse("x = 3, y = 3\n4o!").is_err());
+        assert!(parse("x = 3, y = 3\n2bo$3bo!").is_err());
+        assert!(parse("x = 3, y = 3\no3$o!").is_err());
+        // Without allocating a cell for each count, nor overflowing it.
+        assert!(parse("x = 3, y = 3\n99999999999o!").is_err());
+        assert!(parse("x = 3, y = 3\n999999999999999999999999o!").is_err());
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(16);
+
+        for &(columns, rows) in &[(1, 1), (5, 3), (40, 30), (200, 4)] {
+            let mut board = Board::new(columns, rows);
+            for y in 0..rows {
+                for x in 0..columns {
+                    board.set(x, y, rng.gen_bool(0.3));
+                }
+            }
+
+            let rule: LifeRule = "B36/S23".parse().unwrap();
+            let text = encode(&board, Some(&rule));
+            assert!(text.lines().all(|line| line.len() <= LINE_WIDTH));
+
+            let pattern = parse(&text).unwrap();
+            assert_eq!((pattern.width, pattern.height), (columns, rows));
+            assert_eq!(pattern.rule, Some(rule.to_string()));
+
+            let mut decoded = Board::new(columns, rows);
+            pattern.place(&mut decoded, 0, 0);
+            assert_eq!(decoded.cells(), board.cells(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn empty_board() {
+        let board = Board::new(4, 4);
+        assert_eq!(encode(&board, None), "x = 4, y = 4\n!\n");
+        assert!(parse(&encode(&board, None)).unwrap().cells.is_empty());
+    }
+}
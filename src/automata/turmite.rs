@@ -0,0 +1,506 @@
+//! Turmites: ants on a grid of coloured cells, turning according to the colour under them and
+//! their own state. Langton's ant is the simplest one.
+
+use std::fmt;
+
+/// The turns an ant can make on one kind of grid.
+pub trait Turning: Copy + Eq + fmt::Debug + 'static {
+    /// The number of directions an ant can head in, evenly spread around a cell.
+    const DIRECTIONS: u8;
+
+    /// The turns by how rule strings write them.
+    const NAMES: &'static [(&'static str, Self)];
+
+    /// Rules worth a look, by name: `FromStr` takes the names as well as the rules.
+    const PRESETS: &'static [(&'static str, &'static str)];
+
+    /// How many directions clockwise the turn goes, from 0 to `DIRECTIONS - 1`.
+    fn clockwise(self) -> u8;
+
+    /// The turn going `clockwise` directions clockwise, modulo `DIRECTIONS`.
+    fn from_clockwise(clockwise: u8) -> Self {
+        let clockwise = clockwise % Self::DIRECTIONS;
+        Self::NAMES
+            .iter()
+            .map(|&(_, turn)| turn)
+            .find(|turn| turn.clockwise() == clockwise)
+            .expect("every direction has a turn")
+    }
+
+    /// The turn that undoes this one.
+    fn inverse(self) -> Self {
+        Self::from_clockwise(Self::DIRECTIONS - self.clockwise())
+    }
+
+    fn name(self) -> &'static str {
+        Self::NAMES
+            .iter()
+            .find(|&&(_, turn)| turn == self)
+            .map(|&(name, _)| name)
+            .expect("every turn has a name")
+    }
+
+    /// The turn as written in turmite tables: 1 for none, then doubling with every direction
+    /// clockwise.
+    fn from_code(code: u8) -> Option<Self> {
+        if code.is_power_of_two() && code.trailing_zeros() < u32::from(Self::DIRECTIONS) {
+            Some(Self::from_clockwise(code.trailing_zeros() as u8))
+        } else {
+            None
+        }
+    }
+
+    fn code(self) -> u8 {
+        1 << self.clockwise()
+    }
+}
+
+/// Which way an ant turns on a grid of squares, relative to where it was heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    /// Keep going straight, N in rule strings.
+    None,
+    Right,
+    /// Head back where it came from, U in rule strings.
+    UTurn,
+    Left,
+}
+
+impl Turning for Turn {
+    const DIRECTIONS: u8 = 4;
+
+    const NAMES: &'static [(&'static str, Self)] = &[
+        ("N", Turn::None),
+        ("R", Turn::Right),
+        ("U", Turn::UTurn),
+        ("L", Turn::Left),
+    ];
+
+    const PRESETS: &'static [(&'static str, &'static str)] = &[
+        ("langton", "RL"),
+        ("chaotic", "RLR"),
+        ("cardioid", "LLRR"),
+        ("square", "LRRRRRLLR"),
+        ("triangle", "RRLLLRLLLRRR"),
+        ("two-state", "{{{1,8,1},{1,8,1}},{{1,2,1},{0,1,0}}}"),
+    ];
+
+    fn clockwise(self) -> u8 {
+        match self {
+            Turn::None => 0,
+            Turn::Right => 1,
+            Turn::UTurn => 2,
+            Turn::Left => 3,
+        }
+    }
+}
+
+/// What an ant does on a cell: paint it, turn, then step forward in a new state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition<T = Turn> {
+    pub write: u8,
+    pub turn: T,
+    pub next: u8,
+}
+
+/// A transition for every state of the ant and colour of the cell, with the turns of squares
+/// (`Turn`) or of another grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurmiteRule<T = Turn> {
+    colours: u8,
+    states: u8,
+    /// By state, then colour.
+    table: Vec<Transition<T>>,
+}
+
+impl<T: Turning> TurmiteRule<T> {
+    pub const PRESETS: &'static [(&'static str, &'static str)] = T::PRESETS;
+
+    /// The multi-colour Langton's ant turning `turns[c]` on colour `c`, which it repaints with the
+    /// next colour. The ant has a single state.
+    pub fn from_turns(turns: &[T]) -> Result<Self, String> {
+        if turns.len() < 2 || turns.len() > usize::from(u8::MAX) {
+            return Err(format!(
+                "expected from 2 to {} turns, got {}",
+                u8::MAX,
+                turns.len()
+            ));
+        }
+
+        let colours = turns.len() as u8;
+        let table = turns
+            .iter()
+            .enumerate()
+            .map(|(colour, &turn)| Transition {
+                write: (colour as u8 + 1) % colours,
+                turn,
+                next: 0,
+            })
+            .collect();
+
+        Ok(TurmiteRule {
+            colours,
+            states: 1,
+            table,
+        })
+    }
+
+    /// The rule of `table`, by state then colour.
+    pub fn from_table(table: Vec<Vec<Transition<T>>>) -> Result<Self, String> {
+        let states = table.len();
+        let colours = table.first().map(Vec::len).unwrap_or(0);
+
+        if states == 0 || states > usize::from(u8::MAX) {
+            return Err(format!(
+                "expected from 1 to {} states, got {}",
+                u8::MAX,
+                states
+            ));
+        }
+        if colours < 2 || colours > usize::from(u8::MAX) {
+            return Err(format!(
+                "expected from 2 to {} colours, got {}",
+                u8::MAX,
+                colours
+            ));
+        }
+        if let Some(state) = table.iter().position(|row| row.len() != colours) {
+            return Err(format!(
+                "state {} has {} colours instead of {}",
+                state,
+                table[state].len(),
+                colours
+            ));
+        }
+
+        let table: Vec<Transition<T>> = table.into_iter().flatten().collect();
+        if let Some(transition) = table
+            .iter()
+            .find(|t| usize::from(t.write) >= colours || usize::from(t.next) >= states)
+        {
+            return Err(format!(
+                "{:?} writes a colour or moves to a state that does not exist",
+                transition
+            ));
+        }
+
+        Ok(TurmiteRule {
+            colours: colours as u8,
+            states: states as u8,
+            table,
+        })
+    }
+
+    /// The number of cell colours, numbered from 0, the colour of every cell at first.
+    pub fn colours(&self) -> u8 {
+        self.colours
+    }
+
+    /// The number of ant states, numbered from 0, the state of every ant at first.
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    pub fn transition(&self, state: u8, colour: u8) -> Transition<T> {
+        self.table[usize::from(state) * usize::from(self.colours) + usize::from(colour)]
+    }
+
+    /// Whether every step can be undone: no two transitions write the same colour and move to the
+    /// same state. Always true of rule strings.
+    pub fn is_reversible(&self) -> bool {
+        (0..self.states).all(|state| {
+            (0..self.colours).all(|written| self.preimages(state, written).count() <= 1)
+        })
+    }
+
+    /// What the ant was doing before the transition that left it in `state` on a cell of colour
+    /// `written`: its former state, the former colour of the cell, and how it turned. `None` when
+    /// no transition, or more than one, could have led there.
+    pub fn reverse(&self, state: u8, written: u8) -> Option<(u8, u8, T)> {
+        let mut preimages = self.preimages(state, written);
+
+        match (preimages.next(), preimages.next()) {
+            (Some(preimage), None) => Some(preimage),
+            _ => None,
+        }
+    }
+
+    fn preimages(&self, state: u8, written: u8) -> impl Iterator<Item = (u8, u8, T)> + '_ {
+        let colours = usize::from(self.colours);
+
+        self.table
+            .iter()
+            .enumerate()
+            .filter(move |(_, t)| t.next == state && t.write == written)
+            .map(move |(idx, t)| ((idx / colours) as u8, (idx % colours) as u8, t.turn))
+    }
+
+    /// Whether the rule is a plain rule string: one state, each colour painted over with the next.
+    fn is_ant(&self) -> bool {
+        self.states == 1
+            && self.table.iter().enumerate().all(|(colour, transition)| {
+                transition.next == 0
+                    && usize::from(transition.write) == (colour + 1) % self.table.len()
+            })
+    }
+}
+
+/// Either a rule string of turns, one per colour, like `RL` or `LLRR` on squares, a table of
+/// `{write, turn, next}` transitions by state then colour, with turns coded as in
+/// `Turning::code`, like `{{{1,2,0},{0,8,0}}}`, or a preset name.
+impl<T: Turning> std::str::FromStr for TurmiteRule<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(&(_, rule)) = T::PRESETS.iter().find(|(name, _)| *name == s) {
+            return rule.parse();
+        }
+
+        if s.starts_with('{') {
+            return TurmiteRule::from_table(parse_table(s)?);
+        }
+
+        let invalid = || {
+            let names: Vec<_> = T::NAMES.iter().map(|&(name, _)| name).collect();
+            format!(
+                "expected a rule string of {}, a table or a preset, got {:?}",
+                names.join(", "),
+                s
+            )
+        };
+
+        let upper = s.to_ascii_uppercase();
+        let mut rest = upper.as_str();
+        let mut turns = Vec::new();
+
+        while !rest.is_empty() {
+            let &(name, turn) = T::NAMES
+                .iter()
+                .filter(|(name, _)| rest.starts_with(name))
+                .max_by_key(|(name, _)| name.len())
+                .ok_or_else(invalid)?;
+
+            turns.push(turn);
+            rest = &rest[name.len()..];
+        }
+
+        TurmiteRule::from_turns(&turns)
+    }
+}
+
+impl<T: Turning> fmt::Display for TurmiteRule<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ant() {
+            for transition in &self.table {
+                write!(f, "{}", transition.turn.name())?;
+            }
+            return Ok(());
+        }
+
+        write!(f, "{{")?;
+        for (idx, state) in self.table.chunks(usize::from(self.colours)).enumerate() {
+            if idx > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{{")?;
+            for (idx, t) in state.iter().enumerate() {
+                if idx > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "{{{},{},{}}}", t.write, t.turn.code(), t.next)?;
+            }
+            write!(f, "}}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// The transitions of a `{{{write, turn, next}, ...}, ...}` table, by state then colour.
+fn parse_table<T: Turning>(s: &str) -> Result<Vec<Vec<Transition<T>>>, String> {
+    let invalid = || format!("invalid turmite table {:?}", s);
+    let mut states = Vec::new();
+    let mut state = Vec::new();
+    let mut numbers = Vec::new();
+    let mut number = String::new();
+    let mut depth = 0;
+    let mut closed = false;
+
+    for c in s.chars().filter(|c| !c.is_whitespace()) {
+        // Nothing may follow the outermost closing brace.
+        if closed {
+            return Err(invalid());
+        }
+
+        match c {
+            '{' => {
+                depth += 1;
+                if depth > 3 {
+                    return Err(invalid());
+                }
+            }
+            '}' | ',' => {
+                if !number.is_empty() {
+                    numbers.push(number.parse::<u8>().map_err(|_| invalid())?);
+                    number.clear();
+                }
+
+                if c == ',' {
+                    continue;
+                }
+
+                match depth {
+                    3 => match numbers[..] {
+                        [write, turn, next] => {
+                            let turn = T::from_code(turn).ok_or_else(invalid)?;
+                            state.push(Transition { write, turn, next });
+                            numbers.clear();
+                        }
+                        _ => return Err(invalid()),
+                    },
+                    2 => states.push(std::mem::take(&mut state)),
+                    1 => (),
+                    _ => return Err(invalid()),
+                }
+                depth -= 1;
+                closed = depth == 0;
+            }
+            '0'..='9' if depth == 3 => number.push(c),
+            _ => return Err(invalid()),
+        }
+    }
+
+    if depth != 0 {
+        return Err(invalid());
+    }
+
+    Ok(states)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(s: &str) -> TurmiteRule {
+        s.parse().unwrap()
+    }
+
+    fn turns(rule: &TurmiteRule) -> Vec<Turn> {
+        (0..rule.colours())
+            .map(|colour| rule.transition(0, colour).turn)
+            .collect()
+    }
+
+    #[test]
+    fn rule_strings() {
+        use Turn::*;
+
+        let langton = rule("RL");
+        assert_eq!((langton.colours(), langton.states()), (2, 1));
+        assert_eq!(turns(&langton), [Right, Left]);
+        assert_eq!(
+            langton.transition(0, 0),
+            Transition {
+                write: 1,
+                turn: Right,
+                next: 0
+            }
+        );
+        assert_eq!(langton.transition(0, 1).write, 0);
+
+        assert_eq!(turns(&rule("LLRR")), [Left, Left, Right, Right]);
+        assert_eq!(rule("RRLLLRLLLRRR").colours(), 12);
+        assert_eq!(turns(&rule("NUrl")), [None, UTurn, Right, Left]);
+    }
+
+    #[test]
+    fn presets() {
+        assert_eq!(rule("langton"), rule("RL"));
+        assert_eq!(rule("triangle"), rule("RRLLLRLLLRRR"));
+
+        for &(name, _) in TurmiteRule::<Turn>::PRESETS {
+            assert!(name.parse::<TurmiteRule>().is_ok(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn tables() {
+        // Langton's ant, written out.
+        assert_eq!(rule("{{{1, 2, 0}, {0, 8, 0}}}"), rule("RL"));
+
+        let two_state = rule("two-state");
+        assert_eq!((two_state.colours(), two_state.states()), (2, 2));
+        assert_eq!(
+            two_state.transition(1, 1),
+            Transition {
+                write: 0,
+                turn: Turn::None,
+                next: 0
+            }
+        );
+        assert_eq!(two_state.transition(0, 0).turn, Turn::Left);
+        assert_eq!(two_state.transition(1, 0).turn, Turn::Right);
+    }
+
+    #[test]
+    fn display_roundtrip() {
+        for &s in &[
+            "RL",
+            "LLRR",
+            "NURL",
+            "RRLLLRLLLRRR",
+            "{{{1,8,1},{1,8,1}},{{1,2,1},{0,1,0}}}",
+            "{{{1,2,0},{1,8,0}}}",
+        ] {
+            assert_eq!(rule(s).to_string(), s);
+            assert_eq!(rule(&rule(s).to_string()), rule(s));
+        }
+
+        assert_eq!(rule("{{{1,2,0},{0,8,0}}}").to_string(), "RL");
+    }
+
+    #[test]
+    fn reversible() {
+        let langton = rule("RL");
+        assert!(langton.is_reversible());
+        // Left on a cell now painted 0, so it was 1.
+        assert_eq!(langton.reverse(0, 0), Some((0, 1, Turn::Left)));
+        assert_eq!(langton.reverse(0, 1), Some((0, 0, Turn::Right)));
+
+        // Both colours are painted 1 in state 0.
+        let merging = rule("{{{1,2,0},{1,8,0}}}");
+        assert!(!merging.is_reversible());
+        assert_eq!(merging.reverse(0, 1), Option::None);
+        assert_eq!(merging.reverse(0, 0), Option::None);
+
+        // The same in state 0 of the two-state turmite, whose other transitions can be undone.
+        let two_state = rule("two-state");
+        assert!(!two_state.is_reversible());
+        assert_eq!(two_state.reverse(1, 1), Option::None);
+        assert_eq!(two_state.reverse(0, 0), Some((1, 1, Turn::None)));
+    }
+
+    #[test]
+    fn invalid() {
+        for &s in &[
+            "",
+            "R",
+            "RX",
+            "R L",
+            "{}",
+            "{{}}",
+            "{{{1,2,0}}}",
+            "{{{1,2,0},{0,8,0}}",
+            "{{{1,2,0},{0,8}}}",
+            "{{{1,3,0},{0,8,0}}}",
+            "{{{2,2,0},{0,8,0}}}",
+            "{{{1,2,1},{0,8,0}}}",
+            "{{{1,2,0},{0,8,0}},{{1,2,0}}}",
+            "{{{{1,2,0},{0,8,0}}}}",
+            "{{{1,2,0},{0,8,0}}}{{{1,2,0},{0,8,0}}}",
+            "{{{1,2,0},{0,8,0}}},",
+            "{{{1,2,0},{0,8,0}}}x",
+        ] {
+            assert!(s.parse::<TurmiteRule>().is_err(), "{:?}", s);
+        }
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    doodle::run::<doodle::sketches::multistate::Automaton>();
+}
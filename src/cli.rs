@@ -0,0 +1,477 @@
+//! Command line options: a set of flags common to every sketch, plus the parameters each sketch
+//! declares in `Sketch::PARAMS`.
+
+use failure::{bail, format_err};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// A sketch specific command line parameter, passed as `--<name> <value>`.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    pub help: &'static str,
+    /// The value used when the flag is not passed. It must pass `check`.
+    pub default: &'static str,
+    /// Whether a value is one the sketch can use, usually `parses::<T>` for the type it reads the
+    /// parameter as. Run on every value when the command line is parsed.
+    pub check: fn(&str) -> Result<(), String>,
+}
+
+/// Whether `value` parses as a `T`, the `Param::check` of most parameters.
+pub fn parses<T>(value: &str) -> Result<(), String>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.parse::<T>().map(drop).map_err(|err| err.to_string())
+}
+
+/// Whether `value` parses as a `T` greater than zero, for sizes and intervals.
+pub fn positive<T>(value: &str) -> Result<(), String>
+where
+    T: FromStr + Default + PartialOrd,
+    T::Err: std::fmt::Display,
+{
+    match value.parse::<T>() {
+        Ok(value) if value > T::default() => Ok(()),
+        Ok(_) => Err("expected a number greater than zero".to_owned()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// The values of the declared `Param`s for one run.
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    values: HashMap<&'static str, String>,
+}
+
+impl Params {
+    /// The default values of the `declared` parameters.
+    pub fn defaults(declared: &[Param]) -> Self {
+        Params {
+            values: declared
+                .iter()
+                .map(|param| (param.name, param.default.to_owned()))
+                .collect(),
+        }
+    }
+
+    /// The value of the `name` parameter.
+    ///
+    /// Panics if no such parameter was declared, or if the value does not parse as a `T`, which
+    /// the `check` of the parameter should have ruled out.
+    pub fn get<T>(&self, name: &str) -> T
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let value = self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("the --{} parameter is not declared", name));
+
+        value
+            .parse()
+            .unwrap_or_else(|err| panic!("invalid value for --{} ({:?}): {}", name, value, err))
+    }
+
+    /// The value of a parameter given in milliseconds.
+    pub fn millis(&self, name: &str) -> std::time::Duration {
+        std::time::Duration::from_millis(self.get(name))
+    }
+}
+
+/// The parsed command line.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub width: u32,
+    pub height: u32,
+    /// Updates per second, nannou's default when `None`. Also the simulated frame rate in headless
+    /// mode, where it defaults to `DEFAULT_FPS`.
+    pub fps: Option<f64>,
+    pub seed: Option<u64>,
+    pub fullscreen: bool,
+    /// Do not open a window, only step the simulation.
+    pub headless: bool,
+    /// How many updates to run in headless mode.
+    pub steps: usize,
+    /// Where to write the last frame as SVG, on exit.
+    pub svg: Option<PathBuf>,
+    /// Where to write the last frame as PNG, rendered on the CPU, on exit.
+    pub png: Option<PathBuf>,
+    /// The first frame to record, counted in updates.
+    pub record_start: u64,
+    /// How many frames to record, at the simulated frame rate.
+    pub record_frames: u64,
+    /// Where to write the recorded frames as numbered PNGs.
+    pub record_dir: Option<PathBuf>,
+    /// Where to write the recorded frames as an animated GIF.
+    pub gif: Option<PathBuf>,
+    pub help: bool,
+    pub params: Params,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            width: 1024,
+            height: 768,
+            fps: None,
+            seed: None,
+            fullscreen: false,
+            headless: false,
+            steps: 1000,
+            svg: None,
+            png: None,
+            record_start: 0,
+            record_frames: 0,
+            record_dir: None,
+            gif: None,
+            help: false,
+            params: Params::default(),
+        }
+    }
+}
+
+/// The simulated frame rate when `--fps` is not passed.
+pub const DEFAULT_FPS: f64 = 60.0;
+
+/// The flags every sketch accepts, for the `--help` output.
+const COMMON_FLAGS: &[(&str, &str)] = &[
+    (
+        "--size <w>x<h>",
+        "window size in pixels [default: 1024x768]",
+    ),
+    ("--fps <n>", "updates per second"),
+    ("--seed <n>", "random seed, also read from DOODLE_SEED"),
+    ("--fullscreen", "open the window fullscreen"),
+    ("--headless", "step the simulation without opening a window"),
+    (
+        "--steps <n>",
+        "number of updates in headless mode [default: 1000]",
+    ),
+    ("--svg <path>", "write the last frame as SVG on exit"),
+    (
+        "--png <path>",
+        "render the last frame on the CPU to a PNG on exit",
+    ),
+    ("--record-start <n>", "first frame to record [default: 0]"),
+    (
+        "--record-frames <n>",
+        "number of frames to record at the simulated frame rate",
+    ),
+    (
+        "--record-dir <dir>",
+        "write the recorded frames as numbered PNGs",
+    ),
+    (
+        "--gif <path>",
+        "write the recorded frames as an animated GIF",
+    ),
+    ("--help", "print this message"),
+];
+
+impl Options {
+    /// Parse `args`, not including the binary name, then `check` the parameters together. Prints
+    /// the usage of `bin` and exits on `--help` and on errors.
+    pub fn parse_or_exit<I>(
+        bin: &str,
+        declared: &[Param],
+        check: fn(&Params) -> Result<(), String>,
+        args: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let options = Options::parse(declared, args).and_then(|options| {
+            if !options.help {
+                check(&options.params).map_err(|err| format_err!("{}", err))?;
+            }
+            Ok(options)
+        });
+
+        match options {
+            Ok(ref options) if options.help => {
+                println!("{}", usage(bin, declared));
+                std::process::exit(0);
+            }
+            Ok(options) => options,
+            Err(err) => {
+                eprintln!("error: {}\n\n{}", err, usage(bin, declared));
+                std::process::exit(2);
+            }
+        }
+    }
+
+    /// Parse `args`, not including the binary name.
+    pub fn parse<I>(declared: &[Param], args: I) -> Result<Self, failure::Error>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut options = Options {
+            params: Params::defaults(declared),
+            ..Options::default()
+        };
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.find('=') {
+                Some(idx) => (arg[..idx].to_owned(), Some(arg[idx + 1..].to_owned())),
+                None => (arg.clone(), None),
+            };
+
+            if !flag.starts_with("--") {
+                bail!("unexpected argument {:?}", arg);
+            }
+
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format_err!("{} expects a value", flag))
+            };
+
+            let switch = ["--help", "--fullscreen", "--headless"].contains(&flag.as_str());
+            if switch && inline_value.is_some() {
+                bail!("{} does not take a value", flag);
+            }
+
+            match flag.as_str() {
+                "--help" => options.help = true,
+                "--fullscreen" => options.fullscreen = true,
+                "--headless" => options.headless = true,
+                "--size" => {
+                    let value = value()?;
+                    let (width, height) = parse_size(&value)
+                        .ok_or_else(|| format_err!("invalid --size {:?}, expected WxH", value))?;
+                    options.width = width;
+                    options.height = height;
+                }
+                "--fps" => options.fps = Some(parse_flag(&flag, &value()?)?),
+                "--seed" => options.seed = Some(parse_flag(&flag, &value()?)?),
+                "--steps" => options.steps = parse_flag(&flag, &value()?)?,
+                "--svg" => options.svg = Some(value()?.into()),
+                "--png" => options.png = Some(value()?.into()),
+                "--record-start" => options.record_start = parse_flag(&flag, &value()?)?,
+                "--record-frames" => options.record_frames = parse_flag(&flag, &value()?)?,
+                "--record-dir" => options.record_dir = Some(value()?.into()),
+                "--gif" => options.gif = Some(value()?.into()),
+                other => match declared.iter().find(|param| param.name == &other[2..]) {
+                    Some(param) => {
+                        options.params.values.insert(param.name, value()?);
+                    }
+                    None => bail!("unknown flag {}", other),
+                },
+            }
+        }
+
+        if let Some(fps) = options.fps {
+            if !(fps.is_finite() && fps > 0.0) {
+                bail!("--fps must be a positive number, got {}", fps);
+            }
+        }
+
+        for param in declared {
+            let value = &options.params.values[param.name];
+            (param.check)(value).map_err(|err| {
+                format_err!("invalid value for --{} ({:?}): {}", param.name, value, err)
+            })?;
+        }
+
+        Ok(options)
+    }
+
+    /// The time between two simulated updates.
+    pub fn frame_duration(&self) -> std::time::Duration {
+        let fps = self.fps.unwrap_or(DEFAULT_FPS);
+        std::time::Duration::from_nanos((1_000_000_000.0 / fps) as u64)
+    }
+}
+
+/// The `--help` text for a binary with the `declared` parameters.
+pub fn usage(bin: &str, declared: &[Param]) -> String {
+    let mut out = format!("Usage: {} [OPTIONS]\n\nOptions:\n", bin);
+
+    for (flag, help) in COMMON_FLAGS {
+        out.push_str(&format!("    {:<24} {}\n", flag, help));
+    }
+
+    if !declared.is_empty() {
+        out.push_str("\nSketch parameters:\n");
+
+        for param in declared {
+            let flag = format!("--{} <value>", param.name);
+            out.push_str(&format!(
+                "    {:<24} {} [default: {}]\n",
+                flag, param.help, param.default
+            ));
+        }
+    }
+
+    out.push_str("\nKeys:\n");
+
+    for (key, help) in crate::sketch::BINDINGS {
+        out.push_str(&format!("    {:<24} {}\n", key, help));
+    }
+
+    out
+}
+
+fn parse_size(value: &str) -> Option<(u32, u32)> {
+    let mut parts = value.splitn(2, 'x');
+    let width = parts.next()?.parse().ok()?;
+    let height = parts.next()?.parse().ok()?;
+
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    Some((width, height))
+}
+
+fn parse_flag<T>(flag: &str, value: &str) -> Result<T, failure::Error>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|err| format_err!("invalid value for {} ({:?}): {}", flag, value, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARED: &[Param] = &[
+        Param {
+            name: "gap",
+            help: "distance between two points",
+            default: "20",
+            check: positive::<u32>,
+        },
+        Param {
+            name: "label",
+            help: "anything",
+            default: "",
+            check: parses::<String>,
+        },
+    ];
+
+    fn parse(args: &[&str]) -> Result<Options, failure::Error> {
+        Options::parse(DECLARED, args.iter().map(|&arg| arg.to_owned()))
+    }
+
+    fn error(args: &[&str]) -> String {
+        parse(args).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn defaults() {
+        let options = parse(&[]).unwrap();
+        assert_eq!((options.width, options.height), (1024, 768));
+        assert_eq!(options.fps, None);
+        assert!(!options.help && !options.headless && !options.fullscreen);
+        assert_eq!(options.params.get::<u32>("gap"), 20);
+        assert_eq!(options.params.get::<String>("label"), "");
+        assert_eq!(
+            options.frame_duration(),
+            std::time::Duration::from_nanos(16_666_666)
+        );
+    }
+
+    #[test]
+    fn flags() {
+        let options = parse(&[
+            "--size",
+            "640x480",
+            "--fps=30",
+            "--seed",
+            "7",
+            "--headless",
+            "--steps",
+            "12",
+            "--png",
+            "out.png",
+            "--record-start=3",
+            "--record-frames",
+            "4",
+            "--gif",
+            "out.gif",
+        ])
+        .unwrap();
+
+        assert_eq!((options.width, options.height), (640, 480));
+        assert_eq!(options.fps, Some(30.0));
+        assert_eq!(options.seed, Some(7));
+        assert!(options.headless);
+        assert_eq!(options.steps, 12);
+        assert_eq!(options.png, Some(PathBuf::from("out.png")));
+        assert_eq!((options.record_start, options.record_frames), (3, 4));
+        assert_eq!(options.gif, Some(PathBuf::from("out.gif")));
+        assert_eq!(
+            options.frame_duration(),
+            std::time::Duration::from_nanos(33_333_333)
+        );
+    }
+
+    #[test]
+    fn params() {
+        let options = parse(&["--gap", "5", "--label=a=b"]).unwrap();
+        assert_eq!(options.params.get::<u32>("gap"), 5);
+        assert_eq!(options.params.get::<String>("label"), "a=b");
+    }
+
+    #[test]
+    fn help() {
+        assert!(parse(&["--help"]).unwrap().help);
+        // Even with other flags, valid or not.
+        assert!(parse(&["--gap", "3", "--help"]).unwrap().help);
+
+        let usage = usage("rule_30", DECLARED);
+        assert!(usage.starts_with("Usage: rule_30 [OPTIONS]"));
+        assert!(usage.contains("--gap <value>"));
+        assert!(usage.contains("[default: 20]"));
+    }
+
+    #[test]
+    fn bad_values() {
+        assert!(error(&["--size", "640"]).contains("--size"));
+        assert!(error(&["--size", "0x0"]).contains("--size"));
+        assert!(error(&["--size", "640x0"]).contains("--size"));
+        assert!(error(&["--seed", "-1"]).contains("--seed"));
+        assert!(error(&["--steps"]).contains("expects a value"));
+        assert!(error(&["--nope"]).contains("unknown flag --nope"));
+        assert!(error(&["stray"]).contains("unexpected argument"));
+        assert!(error(&["--gap", "wide"]).contains("invalid value for --gap (\"wide\")"));
+        assert!(error(&["--gap=-3"]).contains("--gap"));
+        assert!(error(&["--gap", "0"]).contains("greater than zero"));
+        assert!(error(&["--fullscreen=x"]).contains("does not take a value"));
+        assert!(error(&["--headless=false"]).contains("does not take a value"));
+    }
+
+    #[test]
+    fn positive_values() {
+        assert_eq!(positive::<f32>("0.5"), Ok(()));
+        assert_eq!(positive::<u64>("12"), Ok(()));
+        assert!(positive::<f32>("0").is_err());
+        assert!(positive::<f32>("-2").is_err());
+        assert!(positive::<f32>("NaN").is_err());
+        assert!(positive::<u64>("0").is_err());
+        assert!(positive::<u64>("ten").is_err());
+    }
+
+    #[test]
+    fn fps_must_be_a_positive_number() {
+        for fps in &["0", "-30", "NaN", "inf", "-inf"] {
+            assert!(error(&["--fps", fps]).contains("--fps"), "{}", fps);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not declared")]
+    fn undeclared_param() {
+        parse(&[]).unwrap().params.get::<u32>("width");
+    }
+}
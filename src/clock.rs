@@ -0,0 +1,174 @@
+//! A fixed-timestep clock for simulations that tick at a constant rate, whatever the frame rate.
+
+use std::time::Duration;
+
+/// Turns the variable time between frames into a whole number of fixed-length ticks.
+///
+/// ```ignore
+/// for _ in 0..self.clock.advance(since_last) {
+///     self.tick();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedStep {
+    step: Duration,
+    /// Simulated time not consumed by ticks yet, always less than `step` between frames.
+    accumulated: Duration,
+    speed: f64,
+    paused: bool,
+    /// Single steps requested while paused.
+    pending: u32,
+}
+
+impl FixedStep {
+    /// The most ticks one frame can run. A slower backlog is dropped rather than caught up with.
+    pub const MAX_TICKS: u32 = 8;
+
+    pub fn new(step: Duration) -> Self {
+        assert!(step > Duration::from_secs(0), "the step must not be zero");
+
+        FixedStep {
+            step,
+            accumulated: Duration::from_secs(0),
+            speed: 1.0,
+            paused: false,
+            pending: 0,
+        }
+    }
+
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Account for `since_last` of wall-clock time and return how many ticks to run now.
+    pub fn advance(&mut self, since_last: Duration) -> u32 {
+        let pending = std::mem::replace(&mut self.pending, 0);
+
+        if self.paused {
+            return pending;
+        }
+
+        self.accumulated += since_last.mul_f64(self.speed);
+
+        let step = self.step.as_nanos();
+        let ticks = self.accumulated.as_nanos() / step;
+        let remainder = self.accumulated.as_nanos() % step;
+        self.accumulated = Duration::from_nanos(remainder as u64);
+
+        (ticks.min(u128::from(FixedStep::MAX_TICKS)) as u32).saturating_add(pending)
+    }
+
+    /// How far the simulation is between the last tick and the next, from 0.0 to 1.0, to
+    /// interpolate what is drawn.
+    pub fn alpha(&self) -> f32 {
+        (self.accumulated.as_nanos() as f64 / self.step.as_nanos() as f64) as f32
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Run exactly one tick on the next `advance`, even when paused.
+    pub fn step_once(&mut self) {
+        self.pending += 1;
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Multiply the rate of ticks by `speed`.
+    pub fn set_speed(&mut self, speed: f64) {
+        assert!(speed >= 0.0, "the speed must not be negative");
+        self.speed = speed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn ticks_per_frame() {
+        let mut clock = FixedStep::new(millis(10));
+        assert_eq!(clock.advance(millis(0)), 0);
+        assert_eq!(clock.advance(millis(9)), 0);
+        assert_eq!(clock.advance(millis(1)), 1);
+        assert_eq!(clock.advance(millis(25)), 2);
+        // The 5 ms left over count towards the next tick.
+        assert_eq!(clock.advance(millis(5)), 1);
+        assert_eq!(clock.advance(millis(16)), 1);
+        assert_eq!(clock.advance(millis(16)), 2);
+    }
+
+    #[test]
+    fn catch_up_is_capped() {
+        let mut clock = FixedStep::new(millis(10));
+        assert_eq!(clock.advance(millis(1000)), FixedStep::MAX_TICKS);
+        // The rest of the backlog is gone, not run on the next frames.
+        assert_eq!(clock.advance(millis(0)), 0);
+        assert_eq!(clock.advance(millis(10)), 1);
+    }
+
+    #[test]
+    fn alpha() {
+        let mut clock = FixedStep::new(millis(10));
+        assert_eq!(clock.alpha(), 0.0);
+        clock.advance(millis(4));
+        assert!((clock.alpha() - 0.4).abs() < 1e-6);
+        clock.advance(millis(13));
+        assert!((clock.alpha() - 0.7).abs() < 1e-6);
+        clock.advance(millis(3));
+        assert_eq!(clock.alpha(), 0.0);
+    }
+
+    #[test]
+    fn pause_and_step_once() {
+        let mut clock = FixedStep::new(millis(10));
+        clock.set_paused(true);
+        assert!(clock.is_paused());
+        assert_eq!(clock.advance(millis(100)), 0);
+
+        clock.step_once();
+        clock.step_once();
+        assert_eq!(clock.advance(millis(100)), 2);
+        assert_eq!(clock.advance(millis(100)), 0);
+
+        // Time spent paused is not caught up with.
+        clock.set_paused(false);
+        assert_eq!(clock.advance(millis(10)), 1);
+
+        clock.step_once();
+        assert_eq!(clock.advance(millis(10)), 2);
+    }
+
+    #[test]
+    fn speed() {
+        let mut clock = FixedStep::new(millis(10));
+        assert_eq!(clock.speed(), 1.0);
+
+        clock.set_speed(2.0);
+        assert_eq!(clock.advance(millis(10)), 2);
+
+        clock.set_speed(0.5);
+        assert_eq!(clock.advance(millis(10)), 0);
+        assert_eq!(clock.advance(millis(10)), 1);
+
+        clock.set_speed(0.0);
+        assert_eq!(clock.advance(millis(1000)), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "negative")]
+    fn negative_speed() {
+        FixedStep::new(millis(10)).set_speed(-1.0);
+    }
+}
@@ -0,0 +1,354 @@
+//! A recording draw layer.
+//!
+//! Sketches describe each frame by calling the same builders as on nannou's `Draw` (`ellipse()`,
+//! `rect()`, `line()`, `tri()`, `polygon()`, `background()`), but the shapes are only recorded as
+//! `Primitive`s. The recording is then replayed onto nannou to reach the GPU, or serialised by
+//! the other backends (see `svg`).
+
+use nannou::draw::properties::color::IntoRgba;
+use nannou::geom::Point2;
+use std::cell::RefCell;
+
+pub type Color = nannou::color::Rgba;
+
+/// The color of shapes that were not given one.
+const DEFAULT_COLOR: Color = nannou::color::WHITE;
+
+/// Where an ellipse or a rect sits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    pub center: Point2,
+    pub w: f32,
+    pub h: f32,
+    /// Counter-clockwise, in radians, around `center`.
+    pub rotation: f32,
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Placement {
+            center: Point2 { x: 0.0, y: 0.0 },
+            w: 0.0,
+            h: 0.0,
+            rotation: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ellipse {
+    pub placement: Placement,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rect {
+    pub placement: Placement,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub start: Point2,
+    pub end: Point2,
+    pub thickness: f32,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tri {
+    pub points: [Point2; 3],
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    pub points: Vec<Point2>,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Background {
+    pub color: Color,
+}
+
+/// One recorded shape.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Primitive {
+    Background(Background),
+    Ellipse(Ellipse),
+    Rect(Rect),
+    Line(Line),
+    Tri(Tri),
+    Polygon(Polygon),
+}
+
+/// Records the shapes drawn during one frame, in order.
+#[derive(Debug, Default)]
+pub struct Draw {
+    primitives: RefCell<Vec<Primitive>>,
+}
+
+impl Draw {
+    pub fn new() -> Self {
+        Draw::default()
+    }
+
+    pub fn background(&self) -> Drawing<'_, Background> {
+        self.drawing(Background {
+            color: nannou::color::BLACK,
+        })
+    }
+
+    pub fn ellipse(&self) -> Drawing<'_, Ellipse> {
+        self.drawing(Ellipse {
+            placement: Placement::default(),
+            color: DEFAULT_COLOR,
+        })
+    }
+
+    pub fn rect(&self) -> Drawing<'_, Rect> {
+        self.drawing(Rect {
+            placement: Placement::default(),
+            color: DEFAULT_COLOR,
+        })
+    }
+
+    pub fn line(&self) -> Drawing<'_, Line> {
+        let origin = Point2 { x: 0.0, y: 0.0 };
+
+        self.drawing(Line {
+            start: origin,
+            end: origin,
+            thickness: 1.0,
+            color: DEFAULT_COLOR,
+        })
+    }
+
+    pub fn tri(&self) -> Drawing<'_, Tri> {
+        let origin = Point2 { x: 0.0, y: 0.0 };
+
+        self.drawing(Tri {
+            points: [origin; 3],
+            color: DEFAULT_COLOR,
+        })
+    }
+
+    pub fn polygon(&self) -> Drawing<'_, Polygon> {
+        self.drawing(Polygon {
+            points: Vec::new(),
+            color: DEFAULT_COLOR,
+        })
+    }
+
+    /// The shapes recorded so far.
+    pub fn into_primitives(self) -> Vec<Primitive> {
+        self.primitives.into_inner()
+    }
+
+    fn drawing<T: Shape>(&self, shape: T) -> Drawing<'_, T> {
+        Drawing {
+            draw: self,
+            shape: Some(shape),
+        }
+    }
+}
+
+/// A shape being built. It is recorded when the builder is dropped, like on nannou's `Draw`.
+pub struct Drawing<'a, T: Shape> {
+    draw: &'a Draw,
+    shape: Option<T>,
+}
+
+impl<'a, T: Shape> Drawing<'a, T> {
+    fn map(mut self, f: impl FnOnce(&mut T)) -> Self {
+        if let Some(shape) = self.shape.as_mut() {
+            f(shape);
+        }
+
+        self
+    }
+
+    pub fn color<C: IntoRgba>(self, color: C) -> Self {
+        let color = color.into_rgba();
+        self.map(|shape| *shape.color_mut() = color)
+    }
+}
+
+impl<'a, T: Shape> Drop for Drawing<'a, T> {
+    fn drop(&mut self) {
+        if let Some(shape) = self.shape.take() {
+            self.draw
+                .primitives
+                .borrow_mut()
+                .push(shape.into_primitive());
+        }
+    }
+}
+
+/// The shapes `Draw` knows how to record.
+pub trait Shape {
+    fn color_mut(&mut self) -> &mut Color;
+
+    fn into_primitive(self) -> Primitive;
+}
+
+/// Shapes positioned by a center and a size.
+pub trait Placed: Shape {
+    fn placement_mut(&mut self) -> &mut Placement;
+}
+
+macro_rules! impl_shape {
+    ($shape:ident) => {
+        impl Shape for $shape {
+            fn color_mut(&mut self) -> &mut Color {
+                &mut self.color
+            }
+
+            fn into_primitive(self) -> Primitive {
+                Primitive::$shape(self)
+            }
+        }
+    };
+}
+
+impl_shape!(Background);
+impl_shape!(Ellipse);
+impl_shape!(Rect);
+impl_shape!(Line);
+impl_shape!(Tri);
+impl_shape!(Polygon);
+
+impl Placed for Ellipse {
+    fn placement_mut(&mut self) -> &mut Placement {
+        &mut self.placement
+    }
+}
+
+impl Placed for Rect {
+    fn placement_mut(&mut self) -> &mut Placement {
+        &mut self.placement
+    }
+}
+
+impl<'a, T: Placed> Drawing<'a, T> {
+    pub fn x(self, x: f32) -> Self {
+        self.map(|shape| shape.placement_mut().center.x = x)
+    }
+
+    pub fn y(self, y: f32) -> Self {
+        self.map(|shape| shape.placement_mut().center.y = y)
+    }
+
+    pub fn x_y(self, x: f32, y: f32) -> Self {
+        self.x(x).y(y)
+    }
+
+    pub fn w(self, w: f32) -> Self {
+        self.map(|shape| shape.placement_mut().w = w)
+    }
+
+    pub fn h(self, h: f32) -> Self {
+        self.map(|shape| shape.placement_mut().h = h)
+    }
+
+    pub fn w_h(self, w: f32, h: f32) -> Self {
+        self.w(w).h(h)
+    }
+
+    pub fn width(self, w: f32) -> Self {
+        self.w(w)
+    }
+
+    pub fn height(self, h: f32) -> Self {
+        self.h(h)
+    }
+
+    pub fn radius(self, radius: f32) -> Self {
+        self.w_h(radius * 2.0, radius * 2.0)
+    }
+
+    /// Counter-clockwise rotation around the center, in radians.
+    pub fn rotate(self, radians: f32) -> Self {
+        self.map(|shape| shape.placement_mut().rotation = radians)
+    }
+}
+
+impl<'a> Drawing<'a, Line> {
+    pub fn start(self, start: Point2) -> Self {
+        self.map(|line| line.start = start)
+    }
+
+    pub fn end(self, end: Point2) -> Self {
+        self.map(|line| line.end = end)
+    }
+
+    pub fn points(self, start: Point2, end: Point2) -> Self {
+        self.start(start).end(end)
+    }
+
+    pub fn thickness(self, thickness: f32) -> Self {
+        self.map(|line| line.thickness = thickness)
+    }
+}
+
+impl<'a> Drawing<'a, Tri> {
+    pub fn points<P: Into<Point2>>(self, a: P, b: P, c: P) -> Self {
+        let points = [a.into(), b.into(), c.into()];
+        self.map(|tri| tri.points = points)
+    }
+}
+
+impl<'a> Drawing<'a, Polygon> {
+    pub fn points<I>(self, points: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Point2>,
+    {
+        let points = points.into_iter().map(Into::into).collect();
+        self.map(|polygon| polygon.points = points)
+    }
+}
+
+/// Replay `primitives` onto nannou's `Draw`.
+pub fn to_nannou(primitives: &[Primitive], draw: &nannou::app::Draw) {
+    for primitive in primitives {
+        match primitive {
+            Primitive::Background(background) => {
+                draw.background().color(background.color);
+            }
+            Primitive::Ellipse(ellipse) => {
+                let placement = &ellipse.placement;
+                draw.ellipse()
+                    .x_y(placement.center.x, placement.center.y)
+                    .w_h(placement.w, placement.h)
+                    .rotate(placement.rotation)
+                    .color(ellipse.color);
+            }
+            Primitive::Rect(rect) => {
+                let placement = &rect.placement;
+                draw.rect()
+                    .x_y(placement.center.x, placement.center.y)
+                    .w_h(placement.w, placement.h)
+                    .rotate(placement.rotation)
+                    .color(rect.color);
+            }
+            Primitive::Line(line) => {
+                draw.line()
+                    .start(line.start)
+                    .end(line.end)
+                    .thickness(line.thickness)
+                    .color(line.color);
+            }
+            Primitive::Tri(tri) => {
+                let [a, b, c] = tri.points;
+                draw.tri().points(a, b, c).color(tri.color);
+            }
+            Primitive::Polygon(polygon) => {
+                draw.polygon()
+                    .points(polygon.points.iter().cloned())
+                    .color(polygon.color);
+            }
+        }
+    }
+}
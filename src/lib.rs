@@ -1 +1,17 @@
-pub mod main_with_model;
+pub mod automata;
+pub mod cli;
+pub mod clock;
+pub mod draw;
+pub mod palette;
+pub mod raster;
+pub mod record;
+pub mod registry;
+pub mod rng;
+pub mod sketch;
+pub mod sketches;
+pub mod svg;
+
+pub use crate::cli::{Options, Param, Params};
+pub use crate::draw::Draw;
+pub use crate::registry::Entry;
+pub use crate::sketch::{headless, record, run, Context, Sketch};
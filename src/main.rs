@@ -0,0 +1,3 @@
+fn main() {
+    doodle::registry::launch(doodle::sketches::registry());
+}
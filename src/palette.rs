@@ -0,0 +1,89 @@
+//! Mapping values to colours.
+
+use crate::draw::Color;
+
+/// A gradient between colour stops, sampled from 0.0 to 1.0.
+#[derive(Debug, Clone)]
+pub struct Ramp {
+    /// Sorted by position.
+    stops: Vec<(f32, Color)>,
+}
+
+impl Ramp {
+    /// A ramp through `stops`, given as `(position, colour)` by increasing position. Values before
+    /// the first stop or after the last one get its colour.
+    pub fn new(stops: Vec<(f32, Color)>) -> Self {
+        assert!(!stops.is_empty(), "a ramp needs at least one stop");
+        assert!(
+            stops.windows(2).all(|pair| pair[0].0 <= pair[1].0),
+            "the stops of a ramp must be sorted"
+        );
+
+        Ramp { stops }
+    }
+
+    /// The colour at `value`, interpolated linearly between the stops around it.
+    pub fn sample(&self, value: f32) -> Color {
+        let after = self
+            .stops
+            .iter()
+            .position(|&(position, _)| position > value)
+            .unwrap_or(self.stops.len());
+
+        if after == 0 {
+            return self.stops[0].1;
+        }
+
+        if after == self.stops.len() {
+            return self.stops[after - 1].1;
+        }
+
+        let (start, from) = self.stops[after - 1];
+        let (end, to) = self.stops[after];
+        let t = (value - start) / (end - start);
+        let mix = |from: f32, to: f32| from + (to - from) * t;
+
+        Color::new(
+            mix(from.red, to.red),
+            mix(from.green, to.green),
+            mix(from.blue, to.blue),
+            mix(from.alpha, to.alpha),
+        )
+    }
+}
+
+/// One colour per state of an automaton.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    colors: Vec<Color>,
+}
+
+impl Palette {
+    pub fn new(colors: Vec<Color>) -> Self {
+        assert!(!colors.is_empty(), "a palette needs at least one colour");
+        Palette { colors }
+    }
+
+    /// `count` colours spread evenly along `ramp`, from one end to the other.
+    pub fn from_ramp(ramp: &Ramp, count: usize) -> Self {
+        let last = count.saturating_sub(1).max(1) as f32;
+        Palette::new(
+            (0..count)
+                .map(|idx| ramp.sample(idx as f32 / last))
+                .collect(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// The colour of `state`, wrapping around past the last one.
+    pub fn color(&self, state: usize) -> Color {
+        self.colors[state % self.colors.len()]
+    }
+}
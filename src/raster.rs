@@ -0,0 +1,377 @@
+//! A software rasteriser for recorded frames, so that PNGs can be rendered without a GPU.
+//!
+//! Every primitive is turned into a polygon and filled with the even-odd rule. Anti-aliasing
+//! comes from exact horizontal coverage on `SAMPLES` sub-scanlines per pixel row.
+
+use crate::draw::{Color, Placement, Primitive};
+use nannou::geom::{Point2, Rect};
+
+/// Sub-scanlines per pixel row.
+const SAMPLES: usize = 4;
+
+/// An RGBA frame buffer covering a window `rect`, one pixel per point.
+pub struct Canvas {
+    rect: Rect,
+    width: usize,
+    height: usize,
+    /// Straight (not premultiplied) RGBA, row by row from the top.
+    pixels: Vec<[f32; 4]>,
+    /// Coverage of the row being filled, reused between rows.
+    coverage: Vec<f32>,
+}
+
+impl Canvas {
+    /// A transparent canvas for a window covering `rect`.
+    pub fn new(rect: Rect) -> Self {
+        let width = rect.w().round().max(1.0) as usize;
+        let height = rect.h().round().max(1.0) as usize;
+
+        Canvas {
+            rect,
+            width,
+            height,
+            pixels: vec![[0.0; 4]; width * height],
+            coverage: vec![0.0; width + 1],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Rasterise `primitives` on top of what is already on the canvas.
+    pub fn draw(&mut self, primitives: &[Primitive]) {
+        for primitive in primitives {
+            match primitive {
+                Primitive::Background(background) => {
+                    let color = rgba(background.color);
+
+                    for pixel in self.pixels.iter_mut() {
+                        *pixel = color;
+                    }
+                }
+                Primitive::Ellipse(ellipse) => {
+                    self.fill(&ellipse_outline(&ellipse.placement), ellipse.color)
+                }
+                Primitive::Rect(rect) => self.fill(&rect_outline(&rect.placement), rect.color),
+                Primitive::Line(line) => self.fill(
+                    &line_outline(line.start, line.end, line.thickness),
+                    line.color,
+                ),
+                Primitive::Tri(tri) => self.fill(&tri.points, tri.color),
+                Primitive::Polygon(polygon) => self.fill(&polygon.points, polygon.color),
+            }
+        }
+    }
+
+    /// The canvas as 8 bit RGBA, row by row from the top.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| pixel.iter().map(|channel| (channel * 255.0).round() as u8))
+            .collect()
+    }
+
+    pub fn to_image(&self) -> image::RgbaImage {
+        image::RgbaImage::from_raw(self.width as u32, self.height as u32, self.to_rgba8())
+            .expect("the buffer matches the canvas size")
+    }
+
+    pub fn save_png<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        self.to_image().save(path)
+    }
+
+    /// Fill the polygon with `points` (in window coordinates) with `color`.
+    fn fill(&mut self, points: &[Point2], color: Color) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let points: Vec<(f32, f32)> = points
+            .iter()
+            .map(|point| (point.x - self.rect.left(), self.rect.top() - point.y))
+            .collect();
+
+        let (min_y, max_y) = points
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), &(_, y)| {
+                (min.min(y), max.max(y))
+            });
+
+        let first_row = min_y.floor().max(0.0) as usize;
+        let last_row = (max_y.ceil().max(0.0) as usize).min(self.height);
+        let color = rgba(color);
+        let mut crossings = Vec::with_capacity(points.len());
+
+        for row in first_row..last_row {
+            let (mut min_x, mut max_x) = (self.width, 0);
+
+            for sample in 0..SAMPLES {
+                let y = row as f32 + (sample as f32 + 0.5) / SAMPLES as f32;
+
+                crossings.clear();
+                for (idx, &(ax, ay)) in points.iter().enumerate() {
+                    let (bx, by) = points[(idx + 1) % points.len()];
+
+                    if (ay <= y) != (by <= y) {
+                        crossings.push(ax + (y - ay) / (by - ay) * (bx - ax));
+                    }
+                }
+
+                crossings.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+                for span in crossings.chunks(2) {
+                    if let [start, end] = *span {
+                        let start = start.clamp(0.0, self.width as f32);
+                        let end = end.clamp(0.0, self.width as f32);
+
+                        if start < end {
+                            min_x = min_x.min(start.floor() as usize);
+                            max_x = max_x.max(end.ceil() as usize);
+                            self.cover(start, end);
+                        }
+                    }
+                }
+            }
+
+            for x in min_x..max_x.min(self.width) {
+                let coverage = self.coverage[x] / SAMPLES as f32;
+                self.coverage[x] = 0.0;
+                blend(&mut self.pixels[row * self.width + x], color, coverage);
+            }
+        }
+    }
+
+    /// Add the horizontal coverage of the `start..end` span to the current row.
+    fn cover(&mut self, start: f32, end: f32) {
+        let first = start.floor() as usize;
+        let last = end.ceil() as usize;
+
+        for x in first..last.min(self.width) {
+            let left = (x as f32).max(start);
+            let right = ((x + 1) as f32).min(end);
+            self.coverage[x] += (right - left).max(0.0);
+        }
+    }
+}
+
+/// Render `primitives` for a window covering `rect` and write the result to `path`.
+pub fn save_png<P: AsRef<std::path::Path>>(
+    path: P,
+    primitives: &[Primitive],
+    rect: Rect,
+) -> std::io::Result<()> {
+    let mut canvas = Canvas::new(rect);
+    canvas.draw(primitives);
+    canvas.save_png(path)
+}
+
+/// Source-over blending of `color` with an extra `coverage` factor.
+fn blend(pixel: &mut [f32; 4], color: [f32; 4], coverage: f32) {
+    let alpha = color[3] * coverage.min(1.0);
+    let out_alpha = alpha + pixel[3] * (1.0 - alpha);
+
+    if out_alpha <= 0.0 {
+        return;
+    }
+
+    for channel in 0..3 {
+        pixel[channel] =
+            (color[channel] * alpha + pixel[channel] * pixel[3] * (1.0 - alpha)) / out_alpha;
+    }
+
+    pixel[3] = out_alpha;
+}
+
+fn rgba(color: Color) -> [f32; 4] {
+    let clamp = |channel: f32| channel.clamp(0.0, 1.0);
+    [
+        clamp(color.red),
+        clamp(color.green),
+        clamp(color.blue),
+        clamp(color.alpha),
+    ]
+}
+
+/// Rotate `(x, y)`, relative to the placement's center, and move it in place.
+fn place(placement: &Placement, x: f32, y: f32) -> Point2 {
+    let (sin, cos) = placement.rotation.sin_cos();
+    Point2 {
+        x: placement.center.x + x * cos - y * sin,
+        y: placement.center.y + x * sin + y * cos,
+    }
+}
+
+fn ellipse_outline(placement: &Placement) -> Vec<Point2> {
+    let (rx, ry) = (placement.w.abs() / 2.0, placement.h.abs() / 2.0);
+    // Roughly one segment per two pixels of circumference.
+    let segments = ((rx + ry) * std::f32::consts::PI / 2.0).clamp(16.0, 512.0) as usize;
+
+    (0..segments)
+        .map(|idx| {
+            let angle = idx as f32 / segments as f32 * std::f32::consts::PI * 2.0;
+            place(placement, rx * angle.cos(), ry * angle.sin())
+        })
+        .collect()
+}
+
+fn rect_outline(placement: &Placement) -> Vec<Point2> {
+    let (hw, hh) = (placement.w.abs() / 2.0, placement.h.abs() / 2.0);
+
+    vec![
+        place(placement, -hw, -hh),
+        place(placement, hw, -hh),
+        place(placement, hw, hh),
+        place(placement, -hw, hh),
+    ]
+}
+
+/// A line as a quad, with butt caps.
+fn line_outline(start: Point2, end: Point2, thickness: f32) -> Vec<Point2> {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let length = (dx * dx + dy * dy).sqrt();
+
+    if length == 0.0 {
+        return Vec::new();
+    }
+
+    let (nx, ny) = (
+        -dy / length * thickness / 2.0,
+        dx / length * thickness / 2.0,
+    );
+
+    vec![
+        Point2 {
+            x: start.x + nx,
+            y: start.y + ny,
+        },
+        Point2 {
+            x: end.x + nx,
+            y: end.y + ny,
+        },
+        Point2 {
+            x: end.x - nx,
+            y: end.y - ny,
+        },
+        Point2 {
+            x: start.x - nx,
+            y: start.y - ny,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw::Draw;
+    use nannou::color::Rgba;
+
+    const RED: Rgba = nannou::color::RED;
+
+    /// A `size` by `size` canvas with what `draw` records on it.
+    fn canvas(size: f32, draw: impl FnOnce(&Draw)) -> Canvas {
+        let recording = Draw::new();
+        draw(&recording);
+
+        let mut canvas = Canvas::new(Rect::from_w_h(size, size));
+        canvas.draw(&recording.into_primitives());
+        canvas
+    }
+
+    /// The pixel in column `x` and row `y` from the top left.
+    fn pixel(canvas: &Canvas, x: usize, y: usize) -> [f32; 4] {
+        canvas.pixels[y * canvas.width + x]
+    }
+
+    fn assert_close(actual: [f32; 4], expected: [f32; 4]) {
+        for (a, e) in actual.iter().zip(&expected) {
+            assert!(
+                (a - e).abs() < 1e-4,
+                "{:?} instead of {:?}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn filled_rect() {
+        // From 5 to 15 on both axes, the pixels fully covered.
+        let canvas = canvas(20.0, |draw| {
+            draw.rect().w_h(10.0, 10.0).color(RED);
+        });
+
+        assert_eq!((canvas.width(), canvas.height()), (20, 20));
+        for y in 0..20 {
+            for x in 0..20 {
+                let inside = (5..15).contains(&x) && (5..15).contains(&y);
+                let expected = if inside {
+                    [1.0, 0.0, 0.0, 1.0]
+                } else {
+                    [0.0; 4]
+                };
+                assert_close(pixel(&canvas, x, y), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn anti_aliased_edges() {
+        // From 4.75 to 15.25: a quarter of the edge pixels is covered, and a quarter of a quarter
+        // of the corners.
+        let canvas = canvas(20.0, |draw| {
+            draw.rect().w_h(10.5, 10.5).color(RED);
+        });
+
+        assert_close(pixel(&canvas, 10, 10), [1.0, 0.0, 0.0, 1.0]);
+        assert_close(pixel(&canvas, 4, 10), [1.0, 0.0, 0.0, 0.25]);
+        assert_close(pixel(&canvas, 15, 10), [1.0, 0.0, 0.0, 0.25]);
+        assert_close(pixel(&canvas, 10, 4), [1.0, 0.0, 0.0, 0.25]);
+        assert_close(pixel(&canvas, 10, 15), [1.0, 0.0, 0.0, 0.25]);
+        assert_close(pixel(&canvas, 4, 4), [1.0, 0.0, 0.0, 0.0625]);
+        assert_close(pixel(&canvas, 3, 10), [0.0; 4]);
+    }
+
+    #[test]
+    fn blended_over_the_background() {
+        let canvas = canvas(20.0, |draw| {
+            draw.background().color(Rgba::new(0.0, 0.0, 1.0, 1.0));
+            draw.rect()
+                .w_h(10.0, 10.0)
+                .color(Rgba::new(1.0, 0.0, 0.0, 0.5));
+        });
+
+        assert_close(pixel(&canvas, 10, 10), [0.5, 0.0, 0.5, 1.0]);
+        assert_close(pixel(&canvas, 0, 0), [0.0, 0.0, 1.0, 1.0]);
+        assert_eq!(&canvas.to_rgba8()[..4], &[0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn even_odd_star() {
+        // A pentagram, every other corner of a pentagon joined: the tips are inside once, the
+        // pentagon in the middle twice, so it stays empty.
+        let canvas = canvas(100.0, |draw| {
+            let corners = (0..5).map(|idx| {
+                let angle =
+                    std::f32::consts::FRAC_PI_2 + idx as f32 * 4.0 * std::f32::consts::PI / 5.0;
+                Point2 {
+                    x: 40.0 * angle.cos(),
+                    y: 40.0 * angle.sin(),
+                }
+            });
+            draw.polygon().points(corners).color(RED);
+        });
+
+        // The top tip, 30 above the middle.
+        assert_close(pixel(&canvas, 50, 20), [1.0, 0.0, 0.0, 1.0]);
+        // The middle.
+        assert_close(pixel(&canvas, 50, 50), [0.0; 4]);
+        assert_close(pixel(&canvas, 49, 49), [0.0; 4]);
+        // Outside, between two tips.
+        assert_close(pixel(&canvas, 50, 85), [0.0; 4]);
+    }
+}
@@ -0,0 +1,279 @@
+//! Capture a range of frames to numbered PNGs and/or an animated GIF.
+//!
+//! While recording, the sketch is stepped at a fixed simulated frame rate instead of the wall
+//! clock, so the output does not depend on how fast the machine renders.
+
+use crate::cli::Options;
+use crate::draw::Primitive;
+use crate::raster::Canvas;
+use gif::SetParameter;
+use nannou::geom::Rect;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// NeuQuant speed for the GIF palette quantisation, from 1 (best) to 30 (fastest).
+const GIF_QUANTISATION_SPEED: i32 = 10;
+
+/// The shortest GIF frame delay, in hundredths of a second. Most viewers show frames with
+/// shorter delays for 10 instead, so faster recordings skip frames to keep to real time.
+const MIN_GIF_DELAY: u64 = 2;
+
+pub struct Recorder {
+    /// The first frame to capture, counted in updates since the start.
+    start: u64,
+    /// How many frames to capture.
+    frames: u64,
+    since_last: Duration,
+    /// Where to write `frame-000000.png`, `frame-000001.png`...
+    dir: Option<PathBuf>,
+    gif_path: Option<PathBuf>,
+    /// Opened on the first captured frame, when its size is known.
+    gif: Option<gif::Encoder<std::fs::File>>,
+    /// The next captured frame that goes in the GIF, counted from the first one.
+    next_gif_frame: u64,
+    captured: u64,
+}
+
+impl Recorder {
+    /// The recorder requested on the command line, if any.
+    pub fn from_options(options: &Options) -> Option<Self> {
+        if options.record_frames == 0 || (options.record_dir.is_none() && options.gif.is_none()) {
+            return None;
+        }
+
+        Some(Recorder {
+            start: options.record_start,
+            frames: options.record_frames,
+            since_last: options.frame_duration(),
+            dir: options.record_dir.clone(),
+            gif_path: options.gif.clone(),
+            gif: None,
+            next_gif_frame: 0,
+            captured: 0,
+        })
+    }
+
+    /// The simulated time between two frames.
+    pub fn since_last(&self) -> Duration {
+        self.since_last
+    }
+
+    /// The frame after which the recording is complete.
+    pub fn end(&self) -> u64 {
+        self.start + self.frames
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.captured == self.frames
+    }
+
+    /// Capture the frame shown after `frame` updates, if it is in the recorded range.
+    pub fn capture(
+        &mut self,
+        frame: u64,
+        primitives: &[Primitive],
+        rect: Rect,
+    ) -> std::io::Result<()> {
+        if frame < self.start || frame >= self.end() {
+            return Ok(());
+        }
+
+        let mut canvas = Canvas::new(rect);
+        canvas.draw(primitives);
+
+        if let Some(dir) = &self.dir {
+            std::fs::create_dir_all(dir)?;
+            canvas.save_png(dir.join(format!("frame-{:06}.png", self.captured)))?;
+        }
+
+        // Frames too close to the last one in the GIF are left out of it.
+        let in_gif = self.captured == self.next_gif_frame;
+
+        if let Some(path) = self.gif_path.as_ref().filter(|_| in_gif) {
+            let (width, height) = (canvas.width() as u16, canvas.height() as u16);
+
+            if self.gif.is_none() {
+                let mut encoder =
+                    gif::Encoder::new(std::fs::File::create(path)?, width, height, &[])?;
+                encoder.set(gif::Repeat::Infinite)?;
+                self.gif = Some(encoder);
+            }
+
+            let mut pixels = canvas.to_rgba8();
+            let mut gif_frame =
+                gif::Frame::from_rgba_speed(width, height, &mut pixels, GIF_QUANTISATION_SPEED);
+            let (delay, next) = gif_delay(self.since_last, self.captured);
+            gif_frame.delay = delay as u16;
+            self.next_gif_frame = next;
+
+            if let Some(encoder) = self.gif.as_mut() {
+                encoder.write_frame(&gif_frame)?;
+            }
+        }
+
+        self.captured += 1;
+
+        if self.is_done() {
+            // Dropping the encoder writes the GIF trailer.
+            self.gif = None;
+            eprintln!("recorded {} frames", self.captured);
+        }
+
+        Ok(())
+    }
+}
+
+/// The delay of captured frame `frame` in the GIF, in hundredths of a second, and the next
+/// captured frame that goes in it: frames go on being shown until the one at least
+/// `MIN_GIF_DELAY` later in the timeline, the ones in between being skipped.
+fn gif_delay(since_last: Duration, frame: u64) -> (u64, u64) {
+    // When the frame is shown, rounded to hundredths of a second.
+    let nanos = since_last.as_nanos().max(1);
+    let shown = |frame: u64| (nanos * u128::from(frame) + 5_000_000) / 10_000_000;
+
+    let mut next = frame + 1;
+    while shown(next) - shown(frame) < u128::from(MIN_GIF_DELAY) {
+        next += 1;
+    }
+
+    ((shown(next) - shown(frame)) as u64, next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw::Draw;
+    use std::path::Path;
+
+    /// An empty directory for the files of one test.
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("doodle-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn recorder(start: u64, frames: u64, fps: f64, dir: &Path) -> Recorder {
+        let options = Options {
+            fps: Some(fps),
+            record_start: start,
+            record_frames: frames,
+            record_dir: Some(dir.join("frames")),
+            gif: Some(dir.join("out.gif")),
+            ..Options::default()
+        };
+        Recorder::from_options(&options).unwrap()
+    }
+
+    fn primitives() -> Vec<Primitive> {
+        let draw = Draw::new();
+        draw.background().color(nannou::color::BLUE);
+        draw.rect().w_h(4.0, 4.0);
+        draw.into_primitives()
+    }
+
+    fn gif_delays(path: &Path) -> Vec<u16> {
+        let mut reader = gif::Decoder::new(std::fs::File::open(path).unwrap())
+            .read_info()
+            .unwrap();
+        let mut delays = Vec::new();
+        while let Some(frame) = reader.read_next_frame().unwrap() {
+            delays.push(frame.delay);
+        }
+        delays
+    }
+
+    #[test]
+    fn nothing_to_record() {
+        assert!(Recorder::from_options(&Options::default()).is_none());
+
+        let options = Options {
+            record_frames: 10,
+            ..Options::default()
+        };
+        assert!(Recorder::from_options(&options).is_none());
+    }
+
+    #[test]
+    fn frame_range() {
+        let dir = scratch("frame-range");
+        let mut recorder = recorder(3, 4, 25.0, &dir);
+        assert_eq!(recorder.end(), 7);
+        assert_eq!(recorder.since_last(), Duration::from_millis(40));
+
+        let rect = Rect::from_w_h(16.0, 8.0);
+        for frame in 0..10 {
+            recorder.capture(frame, &primitives(), rect).unwrap();
+            assert_eq!(recorder.is_done(), frame >= 6, "frame {}", frame);
+        }
+
+        // Numbered from the first recorded frame.
+        let mut pngs: Vec<_> = std::fs::read_dir(dir.join("frames"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        pngs.sort();
+        assert_eq!(
+            pngs,
+            [
+                "frame-000000.png",
+                "frame-000001.png",
+                "frame-000002.png",
+                "frame-000003.png"
+            ]
+        );
+
+        let png = image::open(dir.join("frames/frame-000000.png"))
+            .unwrap()
+            .to_rgba();
+        assert_eq!(png.dimensions(), (16, 8));
+
+        assert_eq!(gif_delays(&dir.join("out.gif")), [4, 4, 4, 4]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// At 60 fps, every other frame or so is skipped and the others shown for 2 or 3 hundredths
+    /// of a second, so that the GIF plays in real time.
+    #[test]
+    fn gif_at_60_fps() {
+        let dir = scratch("gif-at-60-fps");
+        let mut recorder = recorder(0, 60, 60.0, &dir);
+
+        for frame in 0..60 {
+            recorder
+                .capture(frame, &primitives(), Rect::from_w_h(8.0, 8.0))
+                .unwrap();
+        }
+
+        let delays = gif_delays(&dir.join("out.gif"));
+        assert!(
+            delays.iter().all(|&delay| delay == 2 || delay == 3),
+            "{:?}",
+            delays
+        );
+        assert_eq!(
+            delays.iter().map(|&delay| u32::from(delay)).sum::<u32>(),
+            100
+        );
+        assert_eq!(delays.len(), 40);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn gif_delays_keep_to_real_time() {
+        let millis = Duration::from_millis;
+
+        // Long enough frames are all kept, their delays rounding errors not adding up.
+        assert_eq!(gif_delay(millis(40), 0), (4, 1));
+        assert_eq!(gif_delay(millis(33), 0), (3, 1));
+        assert_eq!(gif_delay(millis(33), 1), (4, 2));
+        assert_eq!(gif_delay(millis(25), 1), (2, 2));
+
+        // Shorter ones are skipped until `MIN_GIF_DELAY` has passed.
+        let frame = Duration::from_nanos(16_666_666);
+        assert_eq!(gif_delay(frame, 0), (2, 1));
+        assert_eq!(gif_delay(frame, 1), (3, 3));
+        assert_eq!(gif_delay(millis(1), 0), (2, 15));
+        assert_eq!(gif_delay(Duration::from_secs(0), 0).0, MIN_GIF_DELAY);
+    }
+}
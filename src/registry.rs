@@ -0,0 +1,92 @@
+//! A registry of sketches, and the `doodle` launcher that runs them by name.
+
+use crate::cli::{Options, Param, Params};
+use crate::sketch::{self, AnySketch, Context, Sketch};
+
+/// A sketch the launcher knows about.
+pub struct Entry {
+    pub name: &'static str,
+    /// One line, for `doodle list`.
+    pub description: &'static str,
+    pub params: &'static [Param],
+    /// `Sketch::check`, run on the parameters when the command line is parsed.
+    pub check: fn(&Params) -> Result<(), String>,
+    constructor: fn(&mut Context) -> Box<dyn AnySketch>,
+}
+
+impl Entry {
+    pub fn new<S: Sketch>(name: &'static str, description: &'static str) -> Self {
+        Entry {
+            name,
+            description,
+            params: S::PARAMS,
+            check: S::check,
+            constructor: construct::<S>,
+        }
+    }
+
+    /// Build the sketch, as `Sketch::new` would.
+    pub fn build(&self, ctx: &mut Context) -> Box<dyn AnySketch> {
+        (self.constructor)(ctx)
+    }
+}
+
+fn construct<S: Sketch>(ctx: &mut Context) -> Box<dyn AnySketch> {
+    Box::new(S::new(ctx))
+}
+
+/// The entry point of the `doodle` binary: list the `entries`, or run one of them by name with the
+/// rest of the command line. Tab switches to the next entry at runtime.
+pub fn launch(entries: Vec<Entry>) {
+    let mut args = std::env::args().skip(1);
+
+    let name = match args.next() {
+        None => return list(&entries),
+        Some(ref arg) if arg == "list" => return list(&entries),
+        Some(ref arg) if arg == "--help" || arg == "help" => {
+            println!("{}", usage());
+            return;
+        }
+        Some(name) => name,
+    };
+
+    let current = match entries.iter().position(|entry| entry.name == name) {
+        Some(current) => current,
+        None => {
+            eprintln!("error: no sketch named {:?}\n\n{}", name, usage());
+            list(&entries);
+            std::process::exit(2);
+        }
+    };
+
+    let bin = format!("doodle {}", name);
+    let entry = &entries[current];
+    let options = Options::parse_or_exit(&bin, entry.params, entry.check, args);
+
+    sketch::start(entries, current, options);
+}
+
+fn usage() -> String {
+    [
+        "Usage: doodle [list]              list the sketches",
+        "       doodle <sketch> [OPTIONS]  run a sketch, Tab switches to the next one",
+        "       doodle <sketch> --help     the options of a sketch",
+    ]
+    .join("\n")
+}
+
+/// Print the name, description and parameter defaults of each entry.
+fn list(entries: &[Entry]) {
+    println!("Sketches:");
+
+    for entry in entries {
+        println!("    {:<24} {}", entry.name, entry.description);
+
+        for param in entry.params {
+            let flag = format!("--{} {}", param.name, param.default);
+            println!("    {:<24}     {:<24} {}", "", flag, param.help);
+        }
+    }
+
+    println!("\n{}", usage());
+}
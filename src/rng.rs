@@ -0,0 +1,35 @@
+//! Seeded randomness: every sketch draws its random numbers from one `SeededRng`, so a seed fully
+//! determines a run.
+
+use rand::SeedableRng;
+
+/// The generator handed to sketches through their `Context`.
+pub type SeededRng = rand::rngs::StdRng;
+
+/// The environment variable the seed is read from when `--seed` is not passed.
+pub const SEED_VAR: &str = "DOODLE_SEED";
+
+/// The seed passed with `--seed`, or else the one in `DOODLE_SEED`. When neither is set, a random
+/// seed is picked, so that it can still be printed and reused.
+pub fn seed(from_cli: Option<u64>) -> u64 {
+    from_cli
+        .or_else(|| {
+            std::env::var(SEED_VAR).ok().map(|seed| {
+                seed.parse()
+                    .expect("DOODLE_SEED must be a positive integer")
+            })
+        })
+        .unwrap_or_else(rand::random)
+}
+
+pub fn from_seed(seed: u64) -> SeededRng {
+    SeededRng::seed_from_u64(seed)
+}
+
+/// A Perlin noise function seeded from `rng`.
+pub fn perlin(rng: &mut SeededRng) -> nannou::noise::Perlin {
+    use nannou::noise::Seedable;
+    use rand::Rng;
+
+    nannou::noise::Perlin::new().set_seed(rng.gen())
+}
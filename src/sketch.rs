@@ -0,0 +1,652 @@
+//! The `Sketch` trait and the runner shared by the `doodle` launcher and the binaries in `src/bin`.
+
+use crate::cli::{Options, Param, Params};
+use crate::clock::FixedStep;
+use crate::draw::{self, Draw, Primitive};
+use crate::record::Recorder;
+use crate::registry::Entry;
+use crate::rng::{self, SeededRng};
+use crate::{raster, svg};
+use nannou::event::{Key, Update, WindowEvent};
+use nannou::geom::Rect;
+use nannou::prelude::*;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// What a sketch gets to see of the world outside its own model.
+pub struct Context {
+    /// The area the sketch is drawn in, centered on the origin.
+    pub rect: Rect,
+    /// The seed `rng` was created from.
+    pub seed: u64,
+    /// All the randomness of a run must come from here.
+    pub rng: SeededRng,
+    /// The values of the sketch's `PARAMS`.
+    pub params: Params,
+}
+
+impl Context {
+    pub fn new(rect: Rect, seed: u64, params: Params) -> Self {
+        Context {
+            rect,
+            seed,
+            rng: rng::from_seed(seed),
+            params,
+        }
+    }
+
+    /// A context for a window of the size given on the command line.
+    pub fn from_options(options: &Options) -> Self {
+        Context::new(
+            Rect::from_w_h(options.width as f32, options.height as f32),
+            rng::seed(options.seed),
+            options.params.clone(),
+        )
+    }
+}
+
+/// A model together with the callbacks nannou drives it with.
+///
+/// Only `new` and `view` are mandatory, the other hooks do nothing by default. None of them takes
+/// the nannou `App`, so a sketch can be built, stepped and drawn without a window (see `headless`
+/// and `record`).
+pub trait Sketch: Sized + 'static {
+    /// The sketch specific command line parameters, read back from `Context::params`.
+    const PARAMS: &'static [Param] = &[];
+
+    /// Check the values of `PARAMS` together, once each passed its own `Param::check`, for the
+    /// combinations the sketch cannot run with. Run when the command line is parsed.
+    fn check(_params: &Params) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn new(ctx: &mut Context) -> Self;
+
+    /// Advance the simulation by `since_last`.
+    fn update(&mut self, _ctx: &mut Context, _since_last: Duration) {}
+
+    /// Draw the current state. The frame is recorded, so it can be exported as well as shown.
+    fn view(&self, ctx: &Context, draw: &Draw);
+
+    /// Window events, except resizes which go to `resized` and the keys the runner binds (see
+    /// `BINDINGS`).
+    fn event(&mut self, _ctx: &mut Context, _event: &WindowEvent) {}
+
+    /// The clock the simulation ticks on, if any. The runner then pauses, single steps and speeds
+    /// up the simulation through it, rather than by holding back or scaling `update`.
+    fn clock(&mut self) -> Option<&mut FixedStep> {
+        None
+    }
+
+    /// Called after `ctx.rect` changed.
+    fn resized(&mut self, _ctx: &mut Context) {}
+
+    /// Called once, when the app exits.
+    fn teardown(self, _ctx: &mut Context) {}
+}
+
+/// `Sketch` without the constructor, so that the runner can hold any sketch and swap it for
+/// another at runtime. Implemented for every `Sketch`.
+pub trait AnySketch {
+    fn update(&mut self, ctx: &mut Context, since_last: Duration);
+    fn view(&self, ctx: &Context, draw: &Draw);
+    fn event(&mut self, ctx: &mut Context, event: &WindowEvent);
+    fn resized(&mut self, ctx: &mut Context);
+    fn clock(&mut self) -> Option<&mut FixedStep>;
+    fn teardown(self: Box<Self>, ctx: &mut Context);
+}
+
+impl<S: Sketch> AnySketch for S {
+    fn update(&mut self, ctx: &mut Context, since_last: Duration) {
+        Sketch::update(self, ctx, since_last)
+    }
+
+    fn view(&self, ctx: &Context, draw: &Draw) {
+        Sketch::view(self, ctx, draw)
+    }
+
+    fn event(&mut self, ctx: &mut Context, event: &WindowEvent) {
+        Sketch::event(self, ctx, event)
+    }
+
+    fn resized(&mut self, ctx: &mut Context) {
+        Sketch::resized(self, ctx)
+    }
+
+    fn clock(&mut self) -> Option<&mut FixedStep> {
+        Sketch::clock(self)
+    }
+
+    fn teardown(self: Box<Self>, ctx: &mut Context) {
+        Sketch::teardown(*self, ctx)
+    }
+}
+
+/// The keys the runner handles for every sketch, for the `--help` output.
+pub const BINDINGS: &[(&str, &str)] = &[
+    ("space", "pause or resume the simulation"),
+    ("period", "advance by one tick while paused"),
+    ("r", "rebuild the sketch from the same seed"),
+    ("s", "save the frame as SVG and PNG"),
+    ("+ / -", "double or halve the simulation speed"),
+    ("tab", "switch to the next sketch, in the launcher"),
+];
+
+/// The slowest and fastest simulation speeds `+` and `-` reach.
+const SPEED_RANGE: (f64, f64) = (1.0 / 16.0, 16.0);
+
+/// Pause, single step and speed, set from the keyboard.
+#[derive(Debug, Clone, Copy)]
+struct Playback {
+    paused: bool,
+    /// Steps requested since the last update.
+    steps: u32,
+    speed: f64,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Playback {
+            paused: false,
+            steps: 0,
+            speed: 1.0,
+        }
+    }
+}
+
+/// What `start` hands over to `model`, which nannou calls without arguments.
+struct Launch {
+    entries: Vec<Entry>,
+    current: usize,
+    options: Options,
+}
+
+thread_local! {
+    static LAUNCH: RefCell<Option<Launch>> = const { RefCell::new(None) };
+}
+
+/// The running sketch and its context, the model of the nannou app.
+struct Runner {
+    /// The sketches Tab cycles through.
+    entries: Vec<Entry>,
+    /// The parameters of each entry: the command line ones for the first sketch, the defaults for
+    /// the others.
+    params: Vec<Params>,
+    current: usize,
+    ctx: Context,
+    sketch: Box<dyn AnySketch>,
+    /// Number of updates so far.
+    updates: u64,
+    playback: Playback,
+    /// The exports to write on exit.
+    exports: Vec<(Format, PathBuf)>,
+    /// Set until the requested frames are recorded.
+    recorder: Option<Recorder>,
+}
+
+/// The formats a frame can be exported to.
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Svg,
+    /// Rendered by the CPU rasteriser.
+    Png,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Svg => "svg",
+            Format::Png => "png",
+        }
+    }
+
+    /// The exports requested on the command line.
+    fn requested(options: &Options) -> Vec<(Format, PathBuf)> {
+        let svg = options.svg.iter().map(|path| (Format::Svg, path.clone()));
+        let png = options.png.iter().map(|path| (Format::Png, path.clone()));
+        svg.chain(png).collect()
+    }
+}
+
+/// Parse the command line, then open a window and run `S` in it, or step it in headless mode.
+pub fn run<S: Sketch>() {
+    let mut args = std::env::args();
+    let bin = args.next().unwrap_or_default();
+    let options = Options::parse_or_exit(&bin, S::PARAMS, S::check, args);
+
+    // Exports are named after the sketch, here the binary. It lives as long as the process anyway.
+    let name = Path::new(&bin)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "doodle".to_owned());
+    let name: &'static str = Box::leak(name.into_boxed_str());
+
+    start(vec![Entry::new::<S>(name, "")], 0, options);
+}
+
+/// Run `entries[current]` with `options`, parsed for its parameters.
+pub(crate) fn start(entries: Vec<Entry>, current: usize, options: Options) {
+    if options.headless {
+        let mut ctx = Context::from_options(&options);
+        eprintln!("seed: {}", ctx.seed);
+
+        let mut recorder = Recorder::from_options(&options);
+        // Keep going until the recording is complete.
+        let steps = recorder
+            .as_ref()
+            .map(|recorder| recorder.end().saturating_sub(1).max(options.steps as u64))
+            .unwrap_or(options.steps as u64);
+
+        let mut sketch = entries[current].build(&mut ctx);
+
+        for frame in 0..=steps {
+            if let Some(recorder) = recorder.as_mut() {
+                capture(recorder, frame, &*sketch, &ctx);
+            }
+
+            if frame < steps {
+                sketch.update(&mut ctx, options.frame_duration());
+            }
+        }
+
+        for (format, path) in Format::requested(&options) {
+            export(format, &path, &*sketch, &ctx);
+        }
+
+        sketch.teardown(&mut ctx);
+
+        return;
+    }
+
+    LAUNCH.with(|launch| {
+        *launch.borrow_mut() = Some(Launch {
+            entries,
+            current,
+            options,
+        })
+    });
+
+    nannou::app(model)
+        .event(event)
+        .update(update)
+        .view(view)
+        .exit(exit)
+        .run();
+}
+
+/// Build `S` and step it `steps` times by `since_last`, without opening a window.
+pub fn headless<S: Sketch>(ctx: &mut Context, since_last: Duration, steps: usize) -> S {
+    let mut sketch = S::new(ctx);
+
+    for _ in 0..steps {
+        Sketch::update(&mut sketch, ctx, since_last);
+    }
+
+    sketch
+}
+
+/// Record the frame `sketch` currently shows.
+pub fn record<S: AnySketch + ?Sized>(sketch: &S, ctx: &Context) -> Vec<Primitive> {
+    let draw = Draw::new();
+    sketch.view(ctx, &draw);
+    draw.into_primitives()
+}
+
+fn export(format: Format, path: &Path, sketch: &dyn AnySketch, ctx: &Context) {
+    let primitives = record(sketch, ctx);
+    let result = match format {
+        Format::Svg => svg::save(path, &primitives, ctx.rect),
+        Format::Png => raster::save_png(path, &primitives, ctx.rect),
+    };
+
+    match result {
+        Ok(()) => eprintln!("saved {}", path.display()),
+        Err(err) => eprintln!("could not save {}: {}", path.display(), err),
+    }
+}
+
+fn capture(recorder: &mut Recorder, frame: u64, sketch: &dyn AnySketch, ctx: &Context) {
+    if let Err(err) = recorder.capture(frame, &record(sketch, ctx), ctx.rect) {
+        eprintln!("could not record frame {}: {}", frame, err);
+    }
+}
+
+/// A file name for an export of the current frame, e.g. `rule_30-1234-000042.svg`.
+fn export_name(runner: &Runner, format: Format) -> PathBuf {
+    format!(
+        "{}-{}-{:06}.{}",
+        runner.entries[runner.current].name,
+        runner.ctx.seed,
+        runner.updates,
+        format.extension()
+    )
+    .into()
+}
+
+fn model(app: &App) -> Runner {
+    let Launch {
+        entries,
+        current,
+        options,
+    } = LAUNCH
+        .with(|launch| launch.borrow_mut().take())
+        .expect("the runner is started with `start`");
+
+    let mut window = app
+        .new_window()
+        .with_title(entries[current].name)
+        .with_dimensions(options.width, options.height);
+
+    if options.fullscreen {
+        window = window.with_fullscreen(Some(app.primary_monitor()));
+    }
+
+    window.build().unwrap();
+
+    if let Some(fps) = options.fps {
+        app.set_loop_mode(LoopMode::rate_fps(fps));
+    }
+
+    let seed = rng::seed(options.seed);
+    eprintln!("seed: {}", seed);
+
+    let exports = Format::requested(&options);
+    let mut recorder = Recorder::from_options(&options);
+
+    let mut params: Vec<Params> = entries
+        .iter()
+        .map(|entry| Params::defaults(entry.params))
+        .collect();
+    params[current] = options.params;
+
+    let mut ctx = Context::new(app.window_rect(), seed, params[current].clone());
+    let sketch = entries[current].build(&mut ctx);
+
+    if let Some(recorder) = recorder.as_mut() {
+        capture(recorder, 0, &*sketch, &ctx);
+    }
+
+    Runner {
+        entries,
+        params,
+        current,
+        ctx,
+        sketch,
+        updates: 0,
+        playback: Playback::default(),
+        exports,
+        recorder,
+    }
+}
+
+/// Replace the running sketch with `entries[next]`, built from the same seed.
+fn switch(app: &App, runner: &mut Runner, next: usize) {
+    let entry = &runner.entries[next];
+    let mut ctx = Context::new(
+        runner.ctx.rect,
+        runner.ctx.seed,
+        runner.params[next].clone(),
+    );
+    let sketch = entry.build(&mut ctx);
+
+    app.main_window().set_title(entry.name);
+    eprintln!("running {}", entry.name);
+
+    let previous = std::mem::replace(&mut runner.sketch, sketch);
+    let mut previous_ctx = std::mem::replace(&mut runner.ctx, ctx);
+    previous.teardown(&mut previous_ctx);
+
+    runner.current = next;
+    runner.updates = 0;
+}
+
+fn event(app: &App, runner: &mut Runner, event: nannou::Event) {
+    if let nannou::Event::WindowEvent {
+        simple: Some(event),
+        ..
+    } = event
+    {
+        match event {
+            WindowEvent::Resized(size) => {
+                runner.ctx.rect = Rect::from_w_h(size.x, size.y);
+                runner.sketch.resized(&mut runner.ctx);
+            }
+            WindowEvent::KeyPressed(Key::Space) => {
+                runner.playback.paused = !runner.playback.paused;
+                eprintln!(
+                    "{}",
+                    if runner.playback.paused {
+                        "paused"
+                    } else {
+                        "resumed"
+                    }
+                );
+            }
+            WindowEvent::KeyPressed(Key::Period) => runner.playback.steps += 1,
+            WindowEvent::KeyPressed(Key::R) => {
+                let current = runner.current;
+                switch(app, runner, current);
+            }
+            WindowEvent::KeyPressed(Key::S) => {
+                for &format in &[Format::Svg, Format::Png] {
+                    let path = export_name(runner, format);
+                    export(format, &path, &*runner.sketch, &runner.ctx);
+                }
+            }
+            WindowEvent::KeyPressed(Key::Add) | WindowEvent::KeyPressed(Key::Equals) => {
+                set_speed(runner, 2.0)
+            }
+            WindowEvent::KeyPressed(Key::Subtract) | WindowEvent::KeyPressed(Key::Minus) => {
+                set_speed(runner, 0.5)
+            }
+            WindowEvent::KeyPressed(Key::Tab) if runner.entries.len() > 1 => {
+                let next = (runner.current + 1) % runner.entries.len();
+                switch(app, runner, next);
+            }
+            other => runner.sketch.event(&mut runner.ctx, &other),
+        }
+    }
+}
+
+/// Multiply the simulation speed by `factor`, within `SPEED_RANGE`.
+fn set_speed(runner: &mut Runner, factor: f64) {
+    let (min, max) = SPEED_RANGE;
+    runner.playback.speed = (runner.playback.speed * factor).clamp(min, max);
+    eprintln!("speed: x{}", runner.playback.speed);
+}
+
+fn update(_app: &App, runner: &mut Runner, update: Update) {
+    // While recording, the simulation runs at the simulated frame rate, however long frames take.
+    let since_last = runner
+        .recorder
+        .as_ref()
+        .map(Recorder::since_last)
+        .unwrap_or(update.since_last);
+
+    let playback = &mut runner.playback;
+    let steps = std::mem::replace(&mut playback.steps, 0);
+
+    if let Some(clock) = runner.sketch.clock() {
+        clock.set_paused(playback.paused);
+        clock.set_speed(playback.speed);
+
+        for _ in 0..steps {
+            clock.step_once();
+        }
+
+        runner.sketch.update(&mut runner.ctx, since_last);
+    } else if !playback.paused {
+        let since_last = since_last.mul_f64(playback.speed);
+        runner.sketch.update(&mut runner.ctx, since_last);
+    } else if steps > 0 {
+        // Without a clock, a step is one update as long as the last frame.
+        runner.sketch.update(&mut runner.ctx, since_last);
+    } else {
+        return;
+    }
+
+    runner.updates += 1;
+
+    if let Some(recorder) = runner.recorder.as_mut() {
+        capture(recorder, runner.updates, &*runner.sketch, &runner.ctx);
+
+        if recorder.is_done() {
+            runner.recorder = None;
+        }
+    }
+}
+
+fn view(app: &App, runner: &Runner, frame: Frame) -> Frame {
+    let draw = app.draw();
+    draw::to_nannou(&record(&*runner.sketch, &runner.ctx), &draw);
+    draw.to_frame(app, &frame).unwrap();
+
+    frame
+}
+
+fn exit(_app: &App, mut runner: Runner) {
+    for (format, path) in &runner.exports {
+        export(*format, path, &*runner.sketch, &runner.ctx);
+    }
+
+    runner.sketch.teardown(&mut runner.ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sketches::{langton_ant, rule_30};
+    use rand::Rng;
+
+    /// Counts its updates and the time they add up to, drawing a random number at each.
+    struct Counter {
+        rect: Rect,
+        updates: usize,
+        elapsed: Duration,
+        draws: Vec<u32>,
+    }
+
+    impl Sketch for Counter {
+        fn new(ctx: &mut Context) -> Self {
+            Counter {
+                rect: ctx.rect,
+                updates: 0,
+                elapsed: Duration::from_secs(0),
+                draws: Vec::new(),
+            }
+        }
+
+        fn update(&mut self, ctx: &mut Context, since_last: Duration) {
+            self.updates += 1;
+            self.elapsed += since_last;
+            self.draws.push(ctx.rng.gen());
+        }
+
+        fn view(&self, _ctx: &Context, _draw: &Draw) {}
+    }
+
+    fn run(seed: u64) -> Counter {
+        let mut ctx = Context::new(Rect::from_w_h(320.0, 200.0), seed, Params::default());
+        headless(&mut ctx, Duration::from_millis(16), 1000)
+    }
+
+    #[test]
+    fn headless_steps() {
+        let counter = run(3);
+
+        assert_eq!((counter.rect.w(), counter.rect.h()), (320.0, 200.0));
+        assert_eq!(counter.updates, 1000);
+        assert_eq!(counter.elapsed, Duration::from_secs(16));
+    }
+
+    #[test]
+    fn seeded() {
+        assert_eq!(run(3).draws, run(3).draws);
+        assert_ne!(run(3).draws, run(4).draws);
+    }
+
+    /// A context for a `w` by `h` window, with the parameters of `S` set from `args`.
+    fn context<S: Sketch>(w: f32, h: f32, args: &[&str]) -> Context {
+        let args = args.iter().map(|&arg| arg.to_owned());
+        let options = Options::parse(S::PARAMS, args).unwrap();
+        Context::new(Rect::from_w_h(w, h), 7, options.params)
+    }
+
+    /// What the sketch shows after `steps` updates of `since_last` each.
+    fn frame<S: Sketch>(ctx: &mut Context, since_last: Duration, steps: usize) -> Vec<Primitive> {
+        let sketch: S = headless(ctx, since_last, steps);
+        record(&sketch, ctx)
+    }
+
+    fn step_deterministically<S: Sketch>(args: &[&str], since_last: Duration) {
+        let start = frame::<S>(&mut context::<S>(320.0, 200.0, args), since_last, 0);
+        let stepped = frame::<S>(&mut context::<S>(320.0, 200.0, args), since_last, 40);
+        let again = frame::<S>(&mut context::<S>(320.0, 200.0, args), since_last, 40);
+
+        assert_ne!(stepped, start, "{:?}", args);
+        assert_eq!(stepped, again, "{:?}", args);
+
+        // A larger window shows more.
+        let larger = frame::<S>(&mut context::<S>(640.0, 400.0, args), since_last, 40);
+        assert!(larger.len() > stepped.len(), "{:?}", args);
+    }
+
+    #[test]
+    fn rule_30() {
+        // One tick per update.
+        let since_last = Duration::from_millis(100);
+
+        for &mode in &["tape", "history", "life", "hashlife"] {
+            step_deterministically::<rule_30::Lattice>(&["--mode", mode], since_last);
+        }
+    }
+
+    /// Once resized, rule_30 covers the new window as if built for it.
+    #[test]
+    fn rule_30_resized() {
+        let since_last = Duration::from_millis(100);
+
+        for &mode in &["tape", "history", "life", "hashlife"] {
+            let args = ["--mode", mode];
+            let mut ctx = context::<rule_30::Lattice>(320.0, 200.0, &args);
+            let mut sketch: rule_30::Lattice = headless(&mut ctx, since_last, 5);
+
+            ctx.rect = Rect::from_w_h(640.0, 400.0);
+            Sketch::resized(&mut sketch, &mut ctx);
+            for _ in 0..5 {
+                Sketch::update(&mut sketch, &mut ctx, since_last);
+            }
+
+            let built = frame::<rule_30::Lattice>(
+                &mut context::<rule_30::Lattice>(640.0, 400.0, &args),
+                since_last,
+                0,
+            );
+            assert_eq!(record(&sketch, &ctx).len(), built.len(), "{}", mode);
+        }
+    }
+
+    #[test]
+    fn langton_ant() {
+        let since_last = Duration::from_millis(16);
+
+        step_deterministically::<langton_ant::Model>(&[], since_last);
+        step_deterministically::<langton_ant::Model>(
+            &["--tiling", "hex", "--rule", "L2R2"],
+            since_last,
+        );
+    }
+
+    /// Without time passing, the clock never ticks.
+    #[test]
+    fn zero_duration() {
+        let args = ["--mode", "life"];
+        let mut ctx = context::<rule_30::Lattice>(320.0, 200.0, &args);
+        let start = frame::<rule_30::Lattice>(&mut ctx, Duration::from_millis(0), 0);
+
+        let mut ctx = context::<rule_30::Lattice>(320.0, 200.0, &args);
+        let still = frame::<rule_30::Lattice>(&mut ctx, Duration::from_millis(0), 40);
+        assert_eq!(still, start);
+    }
+}
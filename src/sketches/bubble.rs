@@ -0,0 +1,77 @@
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+
+#[derive(Debug)]
+pub struct Model {
+    since_start: std::time::Duration,
+    bubble: Vec<Point2>,
+    radiuses: Vec<f64>,
+    noise: nannou::noise::Perlin,
+}
+
+impl Model {
+    fn bg_color() -> Rgba {
+        nannou::color::BLUE
+    }
+}
+
+impl crate::Sketch for Model {
+    fn new(ctx: &mut crate::Context) -> Self {
+        Model {
+            since_start: std::time::Duration::from_secs(0),
+            bubble: Vec::with_capacity(360),
+            noise: crate::rng::perlin(&mut ctx.rng),
+            radiuses: Vec::with_capacity(300),
+        }
+    }
+
+    fn view(&self, _ctx: &crate::Context, draw: &crate::Draw) {
+        draw.background().color(Self::bg_color());
+
+        for (origin, next) in self.bubble.iter().zip(self.bubble.iter().skip(1)) {
+            draw.line().start(*origin).end(*next);
+        }
+    }
+
+    fn update(&mut self, _ctx: &mut crate::Context, since_last: std::time::Duration) {
+        self.since_start += since_last;
+        self.bubble.clear();
+        self.radiuses.clear();
+
+        let duration_factor = (self.since_start.as_millis() as f64 / 1000.0).sin();
+        let initial = self.noise.get([duration_factor, 0.0]) * 50.0;
+        self.radiuses.push(initial);
+
+        for angle in 0..i32::max_value() {
+            let angle_radians = (angle as f32 / 360.0) * (2.0 * PI);
+
+            let radius = self.noise.get([duration_factor, angle_radians as f64]) * 50.0;
+            self.radiuses.push(radius);
+
+            if angle > 360 && radius.floor() == initial.floor() {
+                break;
+            }
+        }
+
+        for (idx, radius) in self.radiuses.iter().enumerate() {
+            let angle = (idx as f32 / self.radiuses.len() as f32) * TAU;
+            self.bubble
+                .push(polar_to_cartesian(angle, *radius as f32 + 200.0))
+        }
+
+        // for angle in 0..=360 {
+        //     let angle_radians = (angle as f32 / 360.0) * (2.0 * PI);
+        //     let noise = dbg!(model.noise.get([duration_factor, angle_radians as f64]));
+        //     let radius: f32 = noise as f32 * 50.0 + 200.0;
+        //     let point = Point2 {};
+        //     model.bubble.push(point);
+        // }
+    }
+}
+
+fn polar_to_cartesian(angle: f32, radius: f32) -> Point2 {
+    Point2 {
+        x: radius * angle.cos(),
+        y: radius * angle.sin(),
+    }
+}
@@ -0,0 +1,70 @@
+use crate::clock::FixedStep;
+use nannou::prelude::*;
+
+const TICK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(20);
+
+pub struct Model {
+    points: Vec<Point2>,
+    clock: FixedStep,
+}
+
+impl Model {
+    fn tick(&mut self, ctx: &mut crate::Context) {
+        use rand::Rng;
+
+        let w = ctx.rect.w();
+        let h = ctx.rect.h();
+        let rng = &mut ctx.rng;
+        self.points.push(Point2 {
+            x: rng.sample(nannou::rand::distributions::Normal::new(
+                0.0,
+                w as f64 / 10.0,
+            )) as f32,
+            y: rng.sample(nannou::rand::distributions::Normal::new(
+                0.0,
+                h as f64 / 10.0,
+            )) as f32,
+        })
+    }
+}
+
+impl crate::Sketch for Model {
+    fn new(_ctx: &mut crate::Context) -> Self {
+        Model {
+            points: Vec::with_capacity(20_000),
+            clock: FixedStep::new(TICK_INTERVAL),
+        }
+    }
+
+    fn update(&mut self, ctx: &mut crate::Context, since_last: std::time::Duration) {
+        for _ in 0..self.clock.advance(since_last) {
+            self.tick(ctx)
+        }
+    }
+
+    fn clock(&mut self) -> Option<&mut FixedStep> {
+        Some(&mut self.clock)
+    }
+
+    fn view(&self, _ctx: &crate::Context, draw: &crate::Draw) {
+        use nannou::color;
+
+        draw.background().color(color::WHITE);
+
+        for (idx, point) in self.points.iter().enumerate() {
+            // The newest point grows in until the next tick.
+            let size = if idx + 1 == self.points.len() {
+                4.0 * self.clock.alpha()
+            } else {
+                4.0
+            };
+
+            draw.ellipse()
+                .x(point.x)
+                .y(point.y)
+                .w(size)
+                .h(size)
+                .color(color::RED);
+        }
+    }
+}
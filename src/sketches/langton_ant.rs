@@ -0,0 +1,715 @@
+use crate::automata::grid::{Grid, Topology};
+use crate::automata::hex::{self, HexDirection, HexTurn};
+use crate::automata::turmite::{Transition, TurmiteRule, Turn, Turning};
+use crate::clock::FixedStep;
+use crate::palette::{Palette, Ramp};
+use nannou::color::Rgba;
+use nannou::event::{Key, WindowEvent};
+use nannou::prelude::*;
+use rand::Rng;
+use std::fmt;
+
+/// The shape of the cells the ants walk on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tiling {
+    Square,
+    Hex,
+}
+
+impl Tiling {
+    /// The number of directions an ant can head in.
+    fn directions(self) -> u8 {
+        match self {
+            Tiling::Square => Turn::DIRECTIONS,
+            Tiling::Hex => HexTurn::DIRECTIONS,
+        }
+    }
+}
+
+impl std::str::FromStr for Tiling {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "square" => Ok(Tiling::Square),
+            "hex" => Ok(Tiling::Hex),
+            other => Err(format!("expected square or hex, got {:?}", other)),
+        }
+    }
+}
+
+/// A turmite rule with the turns of the tiling, squares or hexagons.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Rule {
+    Square(TurmiteRule<Turn>),
+    Hex(TurmiteRule<HexTurn>),
+}
+
+impl Rule {
+    fn parse(tiling: Tiling, rule: &str) -> Result<Self, String> {
+        match tiling {
+            Tiling::Square => rule.parse().map(Rule::Square),
+            Tiling::Hex => rule.parse().map(Rule::Hex),
+        }
+    }
+
+    fn presets(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Rule::Square(_) => Turn::PRESETS,
+            Rule::Hex(_) => HexTurn::PRESETS,
+        }
+    }
+
+    fn tiling(&self) -> Tiling {
+        match self {
+            Rule::Square(_) => Tiling::Square,
+            Rule::Hex(_) => Tiling::Hex,
+        }
+    }
+
+    fn colours(&self) -> u8 {
+        match self {
+            Rule::Square(rule) => rule.colours(),
+            Rule::Hex(rule) => rule.colours(),
+        }
+    }
+
+    fn is_reversible(&self) -> bool {
+        match self {
+            Rule::Square(rule) => rule.is_reversible(),
+            Rule::Hex(rule) => rule.is_reversible(),
+        }
+    }
+
+    /// The transition for `state` and `colour`, turning by a number of directions clockwise.
+    fn transition(&self, state: u8, colour: u8) -> Transition<u8> {
+        fn clockwise<T: Turning>(transition: Transition<T>) -> Transition<u8> {
+            Transition {
+                write: transition.write,
+                turn: transition.turn.clockwise(),
+                next: transition.next,
+            }
+        }
+
+        match self {
+            Rule::Square(rule) => clockwise(rule.transition(state, colour)),
+            Rule::Hex(rule) => clockwise(rule.transition(state, colour)),
+        }
+    }
+
+    /// See `TurmiteRule::reverse`, the turn given by a number of directions clockwise.
+    fn reverse(&self, state: u8, written: u8) -> Option<(u8, u8, u8)> {
+        match self {
+            Rule::Square(rule) => rule
+                .reverse(state, written)
+                .map(|(state, colour, turn)| (state, colour, turn.clockwise())),
+            Rule::Hex(rule) => rule
+                .reverse(state, written)
+                .map(|(state, colour, turn)| (state, colour, turn.clockwise())),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Square(rule) => write!(f, "{}", rule),
+            Rule::Hex(rule) => write!(f, "{}", rule),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Ant {
+    /// Counted clockwise, from the top on squares and from the right on hexagons.
+    direction: u8,
+    /// Column and row of the cell under the ant.
+    x: usize,
+    y: usize,
+    /// From 0 to the number of states of the rule.
+    state: u8,
+    /// Whether the ant crossed a mirrored edge an odd number of times, so that it turns right
+    /// where the rule says left and the other way around.
+    mirrored: bool,
+}
+
+impl Ant {
+    /// Turn `clockwise` directions clockwise, out of `directions`.
+    fn turn(&mut self, clockwise: u8, directions: u8) {
+        let clockwise = if self.mirrored {
+            directions - clockwise
+        } else {
+            clockwise
+        };
+        self.direction = (self.direction + clockwise) % directions;
+    }
+
+    /// Move to the next cell in the current direction, through the edges of `grid` as `topology`
+    /// connects them. The ant stays where it is at the edge of a bounded grid.
+    fn advance<T>(&mut self, grid: &Grid<T>, tiling: Tiling, topology: Topology) {
+        let step = match tiling {
+            Tiling::Square => {
+                let (dx, dy) = [(0, 1), (1, 0), (0, -1), (-1, 0)][usize::from(self.direction)];
+                grid.step(self.x, self.y, dx, dy, topology)
+            }
+            Tiling::Hex => {
+                let direction = HexDirection::ALL[usize::from(self.direction)];
+                hex::step(grid, self.x, self.y, direction, topology)
+            }
+        };
+
+        if let Some(step) = step {
+            self.x = step.x;
+            self.y = step.y;
+            self.mirrored ^= step.mirrored;
+        }
+    }
+
+    /// Move back to the previous cell, the opposite of `advance`.
+    fn retreat<T>(&mut self, grid: &Grid<T>, tiling: Tiling, topology: Topology) {
+        let direction = self.direction;
+        let directions = tiling.directions();
+        self.direction = (direction + directions / 2) % directions;
+        self.advance(grid, tiling, topology);
+        self.direction = direction;
+    }
+}
+
+/// Steps run in one update while fast-forwarding to `--until`.
+const FAST_FORWARD_STEPS: u64 = 1 << 20;
+
+/// The distance between two rows of hexagons, for hexagons one wide.
+const HEX_ROW_HEIGHT: f32 = 0.866_025_4;
+
+#[derive(Debug)]
+pub struct Model {
+    /// The length of the side of one square in the grid, or the width of one hexagon, in pixels.
+    size: usize,
+    clock: FixedStep,
+    /// Where the cell in the first column and row, at the bottom left, is drawn.
+    origin: Point2,
+    tiling: Tiling,
+    /// The colour of every cell, rows counted from the bottom, from 0 to the number of colours of
+    /// the rule.
+    colours: Grid<u8>,
+    topology: Topology,
+    ants: Vec<Ant>,
+    rule: Rule,
+    /// One colour per cell colour of the rule.
+    palette: Palette,
+    /// Steps of every ant per tick of the clock.
+    steps_per_tick: u64,
+    /// The step to fast-forward to, between renders, before going on at the pace of the clock. 0
+    /// once reached or rewinding.
+    until: u64,
+    /// Steps of every ant since the last reset.
+    step: u64,
+    /// Whether the ticks undo steps rather than take them, back to the start.
+    rewinding: bool,
+}
+
+impl crate::Sketch for Model {
+    const PARAMS: &'static [crate::Param] = &[
+        crate::Param {
+            name: "cell-size",
+            help: "side of one square in the grid, or width of one hexagon, in pixels",
+            default: "9",
+            check: crate::cli::positive::<usize>,
+        },
+        crate::Param {
+            name: "tick-interval",
+            help: "time between two steps of the ants, in milliseconds",
+            default: "12",
+            check: crate::cli::positive::<u64>,
+        },
+        crate::Param {
+            name: "steps-per-tick",
+            help: "steps of every ant per tick, so per frame with a tick interval of a frame or less",
+            default: "1",
+            check: crate::cli::parses::<u64>,
+        },
+        crate::Param {
+            name: "until",
+            help: "step to fast-forward to before drawing at the pace of the ticks, 0 for none",
+            default: "0",
+            check: crate::cli::parses::<u64>,
+        },
+        crate::Param {
+            name: "tiling",
+            help: "shape of the cells: square or hex",
+            default: "square",
+            check: crate::cli::parses::<Tiling>,
+        },
+        crate::Param {
+            name: "topology",
+            help: "how the edges connect: torus, bounded or klein-bottle, squares only",
+            default: "torus",
+            check: crate::cli::parses::<Topology>,
+        },
+        crate::Param {
+            name: "ants",
+            help: "number of ants",
+            default: "6",
+            check: crate::cli::parses::<usize>,
+        },
+        crate::Param {
+            name: "rule",
+            help: "turns by colour like RL or LLRR (L, R, N, U, or L1, L2, R1, R2, N, U on hexagons), a {{{write,turn,next},..},..} turmite table, or a preset",
+            default: "RL",
+            check: check_rule,
+        },
+    ];
+
+    /// The rule must be one of the tiling, and hexagons do not make Klein bottles.
+    fn check(params: &crate::cli::Params) -> Result<(), String> {
+        let tiling: Tiling = params.get("tiling");
+
+        if tiling == Tiling::Hex {
+            hex::check_topology(params.get("topology"))
+                .map_err(|err| format!("invalid --topology for --tiling hex: {}", err))?;
+        }
+
+        let (rule, name): (String, String) = (params.get("rule"), params.get("tiling"));
+        Rule::parse(tiling, &rule)
+            .map(drop)
+            .map_err(|err| format!("invalid --rule ({:?}) for --tiling {}: {}", rule, name, err))
+    }
+
+    fn new(ctx: &mut crate::Context) -> Self {
+        let window_rect = ctx.rect;
+        let size: usize = ctx.params.get("cell-size");
+
+        let tiling: Tiling = ctx.params.get("tiling");
+        let topology: Topology = ctx.params.get("topology");
+
+        let left = window_rect.left() as i16;
+        let bottom = window_rect.bottom().floor() as i16;
+        let (columns, rows, origin) = match tiling {
+            Tiling::Square => {
+                let columns = (left..window_rect.right().floor() as i16)
+                    .step_by(size)
+                    .count();
+                let rows = (bottom..window_rect.top().floor() as i16)
+                    .step_by(size)
+                    .count();
+                let origin = Point2 {
+                    x: f32::from(left) - size as f32 / 2.0,
+                    y: f32::from(bottom) + size as f32 / 2.0,
+                };
+
+                (columns, rows, origin)
+            }
+            Tiling::Hex => {
+                // Hexagon rows overlap by a quarter of their height, and an even number of them
+                // lines up when wrapped around.
+                let size = size as f32;
+                let columns = (window_rect.w() / size) as usize;
+                let rows = (window_rect.h() / (size * HEX_ROW_HEIGHT)) as usize / 2 * 2;
+                let origin = Point2 {
+                    x: window_rect.left() + size / 2.0,
+                    y: window_rect.bottom() + size / 2.0,
+                };
+
+                (columns.max(1), rows.max(2), origin)
+            }
+        };
+
+        let name: String = ctx.params.get("rule");
+        let rule = Rule::parse(tiling, &name)
+            .unwrap_or_else(|err| panic!("invalid value for --rule ({:?}): {}", name, err));
+
+        let mut model = Model {
+            size,
+            clock: FixedStep::new(ctx.params.millis("tick-interval")),
+            origin,
+            tiling,
+            colours: Grid::new(columns, rows, 0),
+            topology,
+            ants: Vec::new(),
+            palette: palette(&rule),
+            rule,
+            steps_per_tick: ctx.params.get("steps-per-tick"),
+            until: ctx.params.get("until"),
+            step: 0,
+            rewinding: false,
+        };
+
+        model.reset(ctx);
+        model
+    }
+
+    fn update(&mut self, _ctx: &mut crate::Context, since_last: std::time::Duration) {
+        let ticks = self.clock.advance(since_last);
+
+        if self.rewinding {
+            for _ in 0..u64::from(ticks).saturating_mul(self.steps_per_tick) {
+                if self.step == 0 {
+                    eprintln!("rewound to step 0");
+                    self.rewinding = false;
+                    break;
+                }
+
+                self.untick();
+            }
+            return;
+        }
+
+        if self.step < self.until && !self.clock.is_paused() {
+            self.run(FAST_FORWARD_STEPS.min(self.until - self.step));
+
+            if self.step == self.until {
+                eprintln!("reached step {}", self.step);
+                // Once only, so that rewinding can be watched.
+                self.until = 0;
+            }
+            return;
+        }
+
+        self.run(u64::from(ticks).saturating_mul(self.steps_per_tick));
+    }
+
+    fn clock(&mut self) -> Option<&mut FixedStep> {
+        Some(&mut self.clock)
+    }
+
+    fn event(&mut self, ctx: &mut crate::Context, event: &WindowEvent) {
+        if let WindowEvent::KeyPressed(Key::B) = event {
+            if self.topology == Topology::Bounded {
+                eprintln!("ants cannot be run backwards on a bounded grid");
+            } else if self.rule.is_reversible() {
+                self.rewinding = !self.rewinding;
+                self.until = 0;
+            } else {
+                eprintln!("{} cannot be run backwards", self.rule);
+            }
+        }
+
+        if let WindowEvent::KeyPressed(Key::N) = event {
+            let presets = self.rule.presets();
+            let tiling = self.rule.tiling();
+            let current = presets
+                .iter()
+                .position(|(_, rule)| Rule::parse(tiling, rule).as_ref() == Ok(&self.rule));
+            let (name, rule) = presets[current.map(|idx| idx + 1).unwrap_or(0) % presets.len()];
+
+            eprintln!("{} ({})", name, rule);
+            self.rule = Rule::parse(tiling, rule).unwrap();
+            self.palette = palette(&self.rule);
+            self.reset(ctx);
+        }
+    }
+
+    fn view(&self, _ctx: &crate::Context, draw: &crate::Draw) {
+        draw.background().color(nannou::color::BLUE);
+        let size = self.size as f32;
+
+        for (idx, &colour) in self.colours.cells().iter().enumerate() {
+            let (x, y) = self.colours.coords(idx);
+            let color = self.palette.color(usize::from(colour));
+
+            match self.tiling {
+                Tiling::Square => {
+                    draw.rect()
+                        .x(self.origin.x + x as f32 * size)
+                        .y(self.origin.y + y as f32 * size)
+                        .w(size / 1.5)
+                        .h(size / 1.5)
+                        .color(color);
+                }
+                Tiling::Hex => {
+                    // Odd rows are shifted half a hexagon to the right.
+                    let center = Point2 {
+                        x: self.origin.x + (x as f32 + 0.5 * (y % 2) as f32) * size,
+                        y: self.origin.y + y as f32 * size * HEX_ROW_HEIGHT,
+                    };
+                    // Pointy-topped, the width across the flat sides being `size`, with a gap.
+                    let radius = size / 3.0f32.sqrt() * 0.85;
+                    let corners = (0..6).map(|corner| {
+                        let angle = PI / 2.0 + corner as f32 * PI / 3.0;
+                        Point2 {
+                            x: center.x + radius * angle.cos(),
+                            y: center.y + radius * angle.sin(),
+                        }
+                    });
+
+                    draw.polygon().points(corners).color(color);
+                }
+            }
+        }
+    }
+}
+
+impl Model {
+    /// Blank cells, and ants in their first state scattered at random.
+    fn reset(&mut self, ctx: &mut crate::Context) {
+        self.colours.fill(0);
+
+        let num_ants: usize = ctx.params.get("ants");
+        self.ants.clear();
+        self.step = 0;
+        self.rewinding = false;
+
+        for _ in 0..num_ants {
+            self.ants.push(Ant {
+                direction: ctx.rng.gen_range(0, self.tiling.directions()),
+                x: ctx.rng.gen_range(0, self.colours.columns()),
+                y: ctx.rng.gen_range(0, self.colours.rows()),
+                state: 0,
+                mirrored: false,
+            })
+        }
+    }
+
+    /// Move every ant `steps` times, in turn.
+    fn run(&mut self, steps: u64) {
+        for _ in 0..steps {
+            self.tick();
+        }
+    }
+
+    /// Move every ant once, in turn.
+    fn tick(&mut self) {
+        let directions = self.tiling.directions();
+
+        for ant in &mut self.ants {
+            // Look up what to do for the colour of the square, paint it, turn, and move forward
+            // one unit. For Langton's ant (RL): at a white square, turn 90° right, at a black
+            // square turn 90° left, flipping the colour of the square.
+            let colour = self.colours.get_mut(ant.x, ant.y);
+            let transition = self.rule.transition(ant.state, *colour);
+
+            *colour = transition.write;
+            ant.turn(transition.turn, directions);
+            ant.state = transition.next;
+            ant.advance(&self.colours, self.tiling, self.topology);
+        }
+
+        self.step += 1;
+    }
+
+    /// Undo the last `tick`: move every ant back, in the reverse order, restoring the colour of
+    /// the cell it left, its direction and its state.
+    ///
+    /// Panics if the rule is not reversible. Wrong on a bounded grid, where ants stopped by an
+    /// edge cannot be told from ants that moved.
+    fn untick(&mut self) {
+        let rule = &self.rule;
+        let directions = self.tiling.directions();
+
+        for ant in self.ants.iter_mut().rev() {
+            ant.retreat(&self.colours, self.tiling, self.topology);
+
+            let colour = self.colours.get_mut(ant.x, ant.y);
+            let (state, previous, turn) = rule
+                .reverse(ant.state, *colour)
+                .unwrap_or_else(|| panic!("{} cannot be run backwards", rule));
+
+            *colour = previous;
+            ant.turn((directions - turn) % directions, directions);
+            ant.state = state;
+        }
+
+        self.step -= 1;
+    }
+}
+
+/// White for blank cells and red for the next colour, as in the two-colour ant, then on through
+/// the hues.
+fn palette(rule: &Rule) -> Palette {
+    let hues = Ramp::new(vec![
+        (0.0, Rgba::new(1.0, 0.0, 0.0, 1.0)),
+        (0.25, Rgba::new(1.0, 0.8, 0.1, 1.0)),
+        (0.5, Rgba::new(0.2, 0.8, 0.3, 1.0)),
+        (0.75, Rgba::new(0.1, 0.6, 0.9, 1.0)),
+        (1.0, Rgba::new(0.6, 0.2, 0.8, 1.0)),
+    ]);
+    let painted = usize::from(rule.colours()) - 1;
+    let last = painted.saturating_sub(1).max(1) as f32;
+
+    let mut colors = vec![nannou::color::WHITE];
+    colors.extend((0..painted).map(|colour| hues.sample(colour as f32 / last)));
+    Palette::new(colors)
+}
+
+/// Whether the `--rule` is one on squares or on hexagons. Which one it must be depends on
+/// `--tiling`, checked in `Sketch::check`.
+fn check_rule(value: &str) -> Result<(), String> {
+    match Rule::parse(Tiling::Square, value) {
+        Ok(_) => Ok(()),
+        Err(err) => Rule::parse(Tiling::Hex, value).map(drop).map_err(|_| err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SeededRng;
+    use rand::SeedableRng;
+
+    fn model(
+        rule: Rule,
+        topology: Topology,
+        columns: usize,
+        rows: usize,
+        rng: &mut SeededRng,
+    ) -> Model {
+        let ants = (0..rng.gen_range(1, 5))
+            .map(|_| Ant {
+                direction: rng.gen_range(0, rule.tiling().directions()),
+                x: rng.gen_range(0, columns),
+                y: rng.gen_range(0, rows),
+                state: 0,
+                mirrored: false,
+            })
+            .collect();
+        let mut colours = Grid::new(columns, rows, 0);
+        for y in 0..rows {
+            for x in 0..columns {
+                *colours.get_mut(x, y) = rng.gen_range(0, rule.colours());
+            }
+        }
+
+        Model {
+            size: 1,
+            clock: FixedStep::new(std::time::Duration::from_millis(1)),
+            origin: Point2 { x: 0.0, y: 0.0 },
+            tiling: rule.tiling(),
+            colours,
+            topology,
+            ants,
+            palette: palette(&rule),
+            rule,
+            steps_per_tick: 1,
+            until: 0,
+            step: 0,
+            rewinding: false,
+        }
+    }
+
+    /// A rule string of 2 to 6 random turns among `names`.
+    fn random_turns<T: Turning>(rng: &mut SeededRng) -> String {
+        (0..rng.gen_range(2, 7))
+            .map(|_| T::NAMES[rng.gen_range(0, T::NAMES.len())].0)
+            .collect()
+    }
+
+    /// N ticks then N unticks, on small grids where the ants wrap around and run into each other,
+    /// whether the grid is a torus or a Klein bottle of squares, or a torus of hexagons.
+    #[test]
+    fn untick_undoes_tick() {
+        let mut rng = SeededRng::seed_from_u64(23);
+
+        for _ in 0..300 {
+            let (rule, topology, columns, rows) = match rng.gen_range(0, 3) {
+                0 => {
+                    let rule = Rule::parse(Tiling::Square, &random_turns::<Turn>(&mut rng));
+                    let topology = [Topology::Torus, Topology::KleinBottle][rng.gen_range(0, 2)];
+                    (rule, topology, rng.gen_range(1, 12), rng.gen_range(1, 12))
+                }
+                _ => {
+                    let rule = Rule::parse(Tiling::Hex, &random_turns::<HexTurn>(&mut rng));
+                    (
+                        rule,
+                        Topology::Torus,
+                        rng.gen_range(1, 12),
+                        rng.gen_range(1, 6) * 2,
+                    )
+                }
+            };
+
+            let mut model = model(rule.unwrap(), topology, columns, rows, &mut rng);
+            let (colours, ants) = (model.colours.clone(), model.ants.clone());
+            let ticks = rng.gen_range(0, 500);
+
+            model.run(ticks);
+            for _ in 0..ticks {
+                model.untick();
+            }
+
+            assert_eq!(model.step, 0);
+            let case = format!("{} on a {}x{} {}", model.rule, columns, rows, topology);
+            assert_eq!(model.colours, colours, "{}", case);
+            assert_eq!(model.ants, ants, "{}", case);
+        }
+    }
+
+    /// Rewinding after `--until` goes back to step 0 and stays there.
+    #[test]
+    fn rewind_after_fast_forward() {
+        use crate::Sketch;
+
+        let mut rng = SeededRng::seed_from_u64(22);
+        let rule = Rule::parse(Tiling::Square, "RL").unwrap();
+        let mut model = model(rule, Topology::Torus, 8, 8, &mut rng);
+        model.until = 50;
+
+        let mut ctx = crate::Context::new(
+            nannou::geom::Rect::from_w_h(8.0, 8.0),
+            22,
+            crate::cli::Params::default(),
+        );
+        let tick = std::time::Duration::from_millis(1);
+
+        model.update(&mut ctx, std::time::Duration::from_millis(0));
+        assert_eq!(model.step, 50);
+
+        model.event(&mut ctx, &WindowEvent::KeyPressed(Key::B));
+        while model.rewinding {
+            model.update(&mut ctx, tick);
+        }
+        assert_eq!(model.step, 0);
+
+        model.update(&mut ctx, std::time::Duration::from_millis(0));
+        assert_eq!(model.step, 0);
+        model.update(&mut ctx, tick);
+        assert_eq!(model.step, 1);
+    }
+
+    #[test]
+    fn params_together() {
+        use crate::Sketch;
+
+        let check = |args: &[&str]| {
+            let args = args.iter().map(|&arg| arg.to_owned());
+            let options = crate::cli::Options::parse(Model::PARAMS, args).unwrap();
+            Model::check(&options.params)
+        };
+
+        assert_eq!(check(&[]), Ok(()));
+        assert_eq!(check(&["--topology", "klein-bottle"]), Ok(()));
+        assert_eq!(check(&["--tiling", "hex", "--rule", "L2R2"]), Ok(()));
+        assert_eq!(
+            check(&["--tiling", "hex", "--rule", "L2R2", "--topology", "bounded"]),
+            Ok(())
+        );
+
+        // Rules of the other tiling.
+        assert!(check(&["--tiling", "hex"]).is_err());
+        assert!(check(&["--rule", "L2R2"]).is_err());
+        assert!(check(&[
+            "--tiling",
+            "hex",
+            "--rule",
+            "L2R2",
+            "--topology",
+            "klein-bottle"
+        ])
+        .unwrap_err()
+        .contains("Klein bottle"));
+    }
+
+    #[test]
+    fn reversible_tables() {
+        let langton: TurmiteRule = "{{{1,2,0},{0,8,0}}}".parse().unwrap();
+        assert_eq!(langton, "RL".parse().unwrap());
+        assert!(langton.is_reversible());
+
+        let hex: TurmiteRule<HexTurn> = "{{{1,2,0},{0,32,0}}}".parse().unwrap();
+        assert_eq!(hex, "R1L1".parse().unwrap());
+        assert!(hex.is_reversible());
+
+        let two_state: TurmiteRule = "two-state".parse().unwrap();
+        assert!(!two_state.is_reversible());
+        assert_eq!(two_state.reverse(1, 1), None);
+    }
+}
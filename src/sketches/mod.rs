@@ -0,0 +1,39 @@
+//! Every sketch, each also built as its own binary in `src/bin`.
+
+use crate::registry::Entry;
+
+pub mod bubble;
+pub mod gaussian_distribution;
+pub mod langton_ant;
+pub mod multistate;
+pub mod nenuphars;
+pub mod net_with_bounding_boxes;
+pub mod noise;
+pub mod old_ones;
+pub mod rule_30;
+pub mod sine_spiral;
+
+/// The sketches the `doodle` launcher can run, in the order Tab cycles through them.
+pub fn registry() -> Vec<Entry> {
+    vec![
+        Entry::new::<bubble::Model>("bubble", "a circle wobbling with Perlin noise"),
+        Entry::new::<gaussian_distribution::Model>(
+            "gaussian_distribution",
+            "dots piling up along a normal distribution",
+        ),
+        Entry::new::<langton_ant::Model>("langton_ant", "Langton's ants on a grid"),
+        Entry::new::<multistate::Automaton>(
+            "multistate",
+            "cyclic automata, Greenberg-Hastings and Brian's Brain",
+        ),
+        Entry::new::<nenuphars::Model>("nenuphars", "nested circles popping up and fading"),
+        Entry::new::<net_with_bounding_boxes::Net>(
+            "net_with_bounding_boxes",
+            "nodes drifting in their boxes, linked to their neighbours",
+        ),
+        Entry::new::<noise::Model>("noise", "a line of Perlin noise"),
+        Entry::new::<old_ones::Octagons>("old_ones", "a tiling of octagons"),
+        Entry::new::<rule_30::Lattice>("rule_30", "elementary and Life-like cellular automata"),
+        Entry::new::<sine_spiral::Model>("sine_spiral", "a spiral of sines"),
+    ]
+}
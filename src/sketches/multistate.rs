@@ -0,0 +1,218 @@
+use crate::automata::life::Neighbourhood;
+use crate::automata::multistate::{MultiRule, StateGrid};
+use crate::automata::Boundary;
+use crate::clock::FixedStep;
+use crate::palette::{Palette, Ramp};
+use nannou::color::Rgba;
+use nannou::event::{Key, WindowEvent};
+use rand::Rng;
+
+/// Cyclic automata and excitable media on a grid of squares, one colour per state.
+pub struct Automaton {
+    /// The length of the side of one square in the grid, in pixels.
+    size: f32,
+    grid: StateGrid,
+    rule: MultiRule,
+    palette: Palette,
+    neighbourhood: Neighbourhood,
+    boundary: Boundary,
+    clock: FixedStep,
+}
+
+impl crate::Sketch for Automaton {
+    const PARAMS: &'static [crate::Param] = &[
+        crate::Param {
+            name: "rule",
+            help: "cyclic, greenberg-hastings or brians-brain",
+            default: "cyclic",
+            check: check_rule,
+        },
+        crate::Param {
+            name: "states",
+            help: "number of states of the cyclic and greenberg-hastings rules",
+            default: "14",
+            check: check_states,
+        },
+        crate::Param {
+            name: "threshold",
+            help: "neighbours needed to advance a cell, for the same rules",
+            default: "1",
+            check: crate::cli::parses::<usize>,
+        },
+        crate::Param {
+            name: "neighbourhood",
+            help: "moore or von-neumann",
+            default: "von-neumann",
+            check: crate::cli::parses::<Neighbourhood>,
+        },
+        crate::Param {
+            name: "boundary",
+            help: "what lies past the edges: periodic, dead, alive or reflective",
+            default: "periodic",
+            check: crate::cli::parses::<Boundary>,
+        },
+        crate::Param {
+            name: "cell-size",
+            help: "side of one square in the grid, in pixels",
+            default: "8",
+            check: crate::cli::positive::<f32>,
+        },
+        crate::Param {
+            name: "tick-interval",
+            help: "time between two generations, in milliseconds",
+            default: "50",
+            check: crate::cli::positive::<u64>,
+        },
+    ];
+
+    fn new(ctx: &mut crate::Context) -> Self {
+        let size: f32 = ctx.params.get("cell-size");
+        let columns = (ctx.rect.w() / size) as usize;
+        let rows = (ctx.rect.h() / size) as usize;
+
+        let name: String = ctx.params.get("rule");
+        let rule = MultiRule::parse(&name, ctx.params.get("states"), ctx.params.get("threshold"))
+            .unwrap_or_else(|err| panic!("invalid value for --rule: {}", err));
+
+        let mut automaton = Automaton {
+            size,
+            grid: StateGrid::new(columns, rows),
+            rule,
+            palette: palette(rule),
+            neighbourhood: ctx.params.get("neighbourhood"),
+            boundary: ctx.params.get("boundary"),
+            clock: FixedStep::new(ctx.params.millis("tick-interval")),
+        };
+
+        automaton.scatter(ctx);
+        automaton
+    }
+
+    fn update(&mut self, _ctx: &mut crate::Context, since_last: std::time::Duration) {
+        for _ in 0..self.clock.advance(since_last) {
+            self.grid.step(self.rule, self.neighbourhood, self.boundary);
+        }
+    }
+
+    fn clock(&mut self) -> Option<&mut FixedStep> {
+        Some(&mut self.clock)
+    }
+
+    fn event(&mut self, ctx: &mut crate::Context, event: &WindowEvent) {
+        if let WindowEvent::KeyPressed(Key::N) = event {
+            let names = MultiRule::NAMES;
+            let current = names.iter().position(|&name| name == self.rule.name());
+            let name = names[current.map(|idx| idx + 1).unwrap_or(0) % names.len()];
+
+            self.rule =
+                MultiRule::parse(name, ctx.params.get("states"), ctx.params.get("threshold"))
+                    .expect("the names parse");
+            self.palette = palette(self.rule);
+            eprintln!("{}", self.rule);
+
+            self.scatter(ctx);
+        }
+    }
+
+    fn view(&self, ctx: &crate::Context, draw: &crate::Draw) {
+        draw.background().color(self.palette.color(0));
+        let top_left = ctx.rect.top_left();
+
+        for y in 0..self.grid.rows() {
+            for x in 0..self.grid.columns() {
+                let state = self.grid.get(x, y);
+
+                // Already the background.
+                if state == 0 {
+                    continue;
+                }
+
+                draw.rect()
+                    .x(top_left.x + (x as f32 + 0.5) * self.size)
+                    .y(top_left.y - (y as f32 + 0.5) * self.size)
+                    .w(self.size * 0.9)
+                    .h(self.size * 0.9)
+                    .color(self.palette.color(usize::from(state)));
+            }
+        }
+    }
+}
+
+impl Automaton {
+    /// Fill the grid with random states suited to the rule.
+    fn scatter(&mut self, ctx: &mut crate::Context) {
+        let states = self.rule.states();
+
+        for y in 0..self.grid.rows() {
+            for x in 0..self.grid.columns() {
+                let state = match self.rule {
+                    MultiRule::BriansBrain => {
+                        if ctx.rng.gen_bool(0.3) {
+                            1
+                        } else {
+                            0
+                        }
+                    }
+                    _ => ctx.rng.gen_range(0, states),
+                };
+
+                self.grid.set(x, y, state);
+            }
+        }
+    }
+}
+
+/// One colour per state of `rule`.
+fn palette(rule: MultiRule) -> Palette {
+    let states = usize::from(rule.states());
+
+    match rule {
+        // Neighbouring states get neighbouring hues.
+        MultiRule::Cyclic { .. } => {
+            let hues = Ramp::new(vec![
+                (0.0, Rgba::new(0.9, 0.1, 0.2, 1.0)),
+                (0.2, Rgba::new(1.0, 0.8, 0.1, 1.0)),
+                (0.4, Rgba::new(0.2, 0.8, 0.3, 1.0)),
+                (0.6, Rgba::new(0.1, 0.8, 0.9, 1.0)),
+                (0.8, Rgba::new(0.2, 0.3, 0.9, 1.0)),
+                (1.0, Rgba::new(0.7, 0.2, 0.8, 1.0)),
+            ]);
+            Palette::from_ramp(&hues, states)
+        }
+        // Dark at rest, bright when excited, then cooling down.
+        MultiRule::GreenbergHastings { .. } => {
+            let refractory = Ramp::new(vec![
+                (0.0, Rgba::new(1.0, 0.5, 0.1, 1.0)),
+                (1.0, Rgba::new(0.2, 0.0, 0.1, 1.0)),
+            ]);
+            let cooling = states.saturating_sub(2);
+            let last = cooling.saturating_sub(1).max(1) as f32;
+
+            let mut colors = vec![
+                Rgba::new(0.05, 0.0, 0.1, 1.0),
+                Rgba::new(1.0, 1.0, 0.8, 1.0),
+            ];
+            colors.extend((0..cooling).map(|state| refractory.sample(state as f32 / last)));
+            Palette::new(colors)
+        }
+        MultiRule::BriansBrain => Palette::new(vec![
+            Rgba::new(0.0, 0.0, 0.05, 1.0),
+            Rgba::new(1.0, 1.0, 1.0, 1.0),
+            Rgba::new(0.2, 0.4, 1.0, 1.0),
+        ]),
+    }
+}
+
+/// Whether `--rule` names a rule, parsed as `new` does, the states and threshold being checked
+/// on their own.
+fn check_rule(value: &str) -> Result<(), String> {
+    MultiRule::parse(value, 2, 1).map(drop)
+}
+
+fn check_states(value: &str) -> Result<(), String> {
+    match value.parse::<u8>() {
+        Ok(states) if states >= 2 => Ok(()),
+        Ok(_) => Err("expected at least 2 states".to_owned()),
+        Err(err) => Err(err.to_string()),
+    }
+}
@@ -0,0 +1,231 @@
+///! inspired by https://www.instagram.com/p/Ba9ApsdFlnV/
+use crate::clock::FixedStep;
+use crate::rng::SeededRng;
+use nannou::draw::properties::color::IntoRgba;
+use nannou::prelude::*;
+use rand::Rng;
+
+/// The lifetime one px of radius is equivalent to.
+const RADIUS_PX_TO_LIFETIME: std::time::Duration = std::time::Duration::from_millis(300);
+
+#[derive(Debug, PartialEq)]
+enum NenupharState {
+    Live,
+    Dead,
+    Dying,
+}
+
+#[derive(PartialEq)]
+enum Palette {
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+}
+
+impl Palette {
+    fn random_except(&self, rng: &mut SeededRng) -> Palette {
+        loop {
+            let new = Palette::random(rng);
+            if &new != self {
+                return new;
+            }
+        }
+    }
+
+    fn random(rng: &mut SeededRng) -> Self {
+        match rng.gen_range(0, 5) {
+            0 => Palette::One,
+            1 => Palette::Two,
+            2 => Palette::Three,
+            3 => Palette::Four,
+            4 => Palette::Five,
+            _ => panic!("color out of range"),
+        }
+    }
+
+    fn to_rgba(&self) -> nannou::color::Rgba {
+        match self {
+            Palette::One => nannou::color::Rgb::new_u8(74, 78, 77),
+            Palette::Two => nannou::color::Rgb::new_u8(14, 154, 167),
+            Palette::Three => nannou::color::Rgb::new_u8(61, 164, 171),
+            Palette::Four => nannou::color::Rgb::new_u8(246, 205, 97),
+            Palette::Five => nannou::color::Rgb::new_u8(254, 138, 113),
+        }
+        .into_rgba()
+    }
+}
+
+struct Nenuphar {
+    color: Palette,
+    center: Point2,
+    apparent_radius: u32,
+    radius: u32,
+    state: NenupharState,
+    lifetime: std::time::Duration,
+    nested: Vec<Nenuphar>,
+}
+
+impl Nenuphar {
+    fn new(center: Point2, radius: u32, parent_color: &Palette, rng: &mut SeededRng) -> Nenuphar {
+        Nenuphar {
+            color: parent_color.random_except(rng),
+            apparent_radius: 0,
+            radius,
+            center,
+            state: NenupharState::Live,
+            lifetime: std::time::Duration::from_millis(0),
+            nested: Vec::with_capacity(5),
+        }
+    }
+
+    fn overlaps(&self, other: &Nenuphar) -> bool {
+        let a_squared = (other.center.y - self.center.y).abs().powi(2);
+        let b_squared = (other.center.x - self.center.x).abs().powi(2);
+        let dist = (a_squared + b_squared).sqrt();
+        dist < (self.radius + other.radius) as f32
+    }
+
+    fn pop_inner(&mut self, rng: &mut SeededRng) {
+        if self.radius < 20 {
+            return;
+        }
+
+        let radius = rng.gen_range(10, (self.radius as f32 * 0.7) as u32);
+        let dist = rng.gen_range(1.0, (self.radius - radius) as f32);
+        let angle = rng.gen_range(0.0, std::f32::consts::PI * 2.0);
+
+        let center = Point2 {
+            x: self.center.x + dist * angle.cos(),
+            y: self.center.y + dist * angle.sin(),
+        };
+
+        let nenuphar = Nenuphar::new(center, radius, &self.color, rng);
+
+        if !self.nested.iter().any(|n| n.overlaps(&nenuphar)) {
+            self.nested.push(nenuphar);
+        }
+    }
+
+    fn update(&mut self, elapsed: std::time::Duration, rng: &mut SeededRng) {
+        self.lifetime += elapsed;
+
+        match self.state {
+            NenupharState::Live => {
+                if self.apparent_radius <= self.radius {
+                    self.apparent_radius = std::cmp::min(
+                        self.radius,
+                        self.apparent_radius + (elapsed.as_millis() as u32 / 6),
+                    );
+                }
+
+                for nested in &mut self.nested {
+                    nested.update(elapsed, rng)
+                }
+
+                self.pop_inner(rng);
+
+                if self.lifetime.as_millis()
+                    > (RADIUS_PX_TO_LIFETIME.as_millis() * self.radius as u128)
+                {
+                    self.state = NenupharState::Dying;
+                }
+            }
+            NenupharState::Dead => (),
+            NenupharState::Dying => {
+                if self.apparent_radius > 0 {
+                    self.apparent_radius -= 1;
+                } else {
+                    self.state = NenupharState::Dead;
+                }
+            }
+        }
+    }
+
+    fn draw(&self, draw: &crate::Draw) {
+        draw.ellipse()
+            .x_y(self.center.x, self.center.y)
+            .color(self.color.to_rgba())
+            .w(self.apparent_radius as f32 * 2.0)
+            .h(self.apparent_radius as f32 * 2.0);
+
+        for nested in &self.nested {
+            nested.draw(draw);
+        }
+    }
+}
+
+pub struct Model {
+    nenuphars: Vec<Nenuphar>,
+    /// Ticks once per attempt at popping a new nenuphar.
+    popup_clock: FixedStep,
+}
+
+impl crate::Sketch for Model {
+    const PARAMS: &'static [crate::Param] = &[crate::Param {
+        name: "popup-interval",
+        help: "time between two new nenuphars, in milliseconds",
+        default: "500",
+        check: crate::cli::positive::<u64>,
+    }];
+
+    fn new(ctx: &mut crate::Context) -> Self {
+        Model {
+            popup_clock: FixedStep::new(ctx.params.millis("popup-interval")),
+            nenuphars: Vec::with_capacity(10),
+        }
+    }
+
+    fn view(&self, _ctx: &crate::Context, draw: &crate::Draw) {
+        draw.background().color(Self::bg_color().to_rgba());
+
+        for nenuphar in self
+            .nenuphars
+            .iter()
+            .filter(|n| n.state != NenupharState::Dead)
+        {
+            nenuphar.draw(draw);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut crate::Context, since_last: std::time::Duration) {
+        for nenuphar in &mut self.nenuphars {
+            nenuphar.update(since_last, &mut ctx.rng);
+        }
+
+        // One at most, however long the frame.
+        if self.popup_clock.advance(since_last) > 0 {
+            self.pop_nenuphar(ctx)
+        }
+    }
+}
+
+impl Model {
+    fn bg_color() -> Palette {
+        Palette::Four
+    }
+
+    fn pop_nenuphar(&mut self, ctx: &mut crate::Context) {
+        let window_rect = ctx.rect;
+        let rng = &mut ctx.rng;
+        // At least 21 wide, however low the window.
+        let max_radius = ((window_rect.h() / 3.0).floor() as u32).max(21);
+        let radius = rng.gen_range(20, max_radius);
+        let position = Point2 {
+            x: rng.gen_range(window_rect.left(), window_rect.right()),
+            y: rng.gen_range(window_rect.bottom(), window_rect.top()),
+        };
+
+        let nenuphar = Nenuphar::new(position, radius, &Self::bg_color(), rng);
+
+        if !self
+            .nenuphars
+            .iter()
+            .filter(|n| n.state != NenupharState::Dead)
+            .any(|n| n.overlaps(&nenuphar))
+        {
+            self.nenuphars.push(nenuphar);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::rng::SeededRng;
+use nannou::prelude::*;
+use rand::Rng;
+
+const SPEED: f32 = 0.005;
+
+struct Node {
+    box_center: Point2,
+    box_radius: f32,
+    current_pos: Point2,
+    current_target: Point2,
+}
+
+impl Node {
+    fn new(box_center: Point2, box_radius: f32, rng: &mut SeededRng) -> Node {
+        let current_pos = Point2 {
+            x: rng.gen_range(box_center.x - box_radius, box_center.x + box_radius),
+            y: rng.gen_range(box_center.y - box_radius, box_center.y + box_radius),
+        };
+        let current_target = Point2 {
+            x: rng.gen_range(box_center.x - box_radius, box_center.x + box_radius),
+            y: rng.gen_range(box_center.y - box_radius, box_center.y + box_radius),
+        };
+        Node {
+            box_center,
+            box_radius,
+            current_pos,
+            current_target,
+        }
+    }
+
+    fn tick(&mut self, elapsed: std::time::Duration, rng: &mut SeededRng) {
+        if self.current_pos.distance(self.current_target) < 2.0 {
+            self.current_target = Point2 {
+                x: rng.gen_range(
+                    self.box_center.x - self.box_radius,
+                    self.box_center.x + self.box_radius,
+                ),
+                y: rng.gen_range(
+                    self.box_center.y - self.box_radius,
+                    self.box_center.y + self.box_radius,
+                ),
+            };
+        }
+
+        let new_x = self.current_pos.x
+            + (self.current_target.x - self.current_pos.x) * (elapsed.as_millis() as f32 * SPEED);
+        let new_y = self.current_pos.y
+            + ((self.current_target.y - self.current_pos.y) * (elapsed.as_millis() as f32 * SPEED));
+        self.current_pos = Point2 { x: new_x, y: new_y };
+    }
+}
+
+pub struct Net {
+    w: f32,
+    h: f32,
+    rows: u32,
+    per_row: usize,
+    nodes: Vec<Node>,
+}
+
+impl crate::Sketch for Net {
+    const PARAMS: &'static [crate::Param] = &[crate::Param {
+        name: "dist",
+        help: "distance between two nodes at rest, in pixels",
+        default: "35",
+        check: crate::cli::positive::<f32>,
+    }];
+
+    fn new(ctx: &mut crate::Context) -> Self {
+        let window_rect = ctx.rect;
+        let dist: f32 = ctx.params.get("dist");
+
+        let per_row = (window_rect.w() / dist + 1.0) as usize;
+
+        let mut nodes = Vec::with_capacity(per_row * 10);
+        let mut cursor = window_rect.top_left();
+
+        let mut rows = 0;
+
+        let is_finished = |cursor: Point2| cursor.y < window_rect.bottom();
+
+        while !is_finished(cursor) {
+            nodes.push(Node::new(cursor, dist / 2.0, &mut ctx.rng));
+
+            if nodes.len() % per_row == 0 {
+                // next row
+                rows += 1;
+                cursor.y -= dist;
+                cursor.x = if rows % 2 == 0 {
+                    window_rect.left()
+                } else {
+                    window_rect.left() + (dist / 2.0)
+                };
+            } else {
+                cursor.x += dist;
+            }
+        }
+
+        Net {
+            nodes,
+            rows,
+            per_row,
+            w: window_rect.w(),
+            h: window_rect.h(),
+        }
+    }
+
+    fn view(&self, _ctx: &crate::Context, draw: &crate::Draw) {
+        draw.background().color(nannou::color::DARK_BLUE);
+        for (idx, node) in self.nodes.iter().enumerate() {
+            // draw.ellipse()
+            //     .color(nannou::color::RED)
+            //     .radius(3.0)
+            //     .x(node.current_pos.x)
+            //     .y(node.current_pos.y)
+            //     .finish()
+            //     .unwrap();
+
+            // draw.ellipse()
+            //     .color(nannou::color::GREEN)
+            //     .radius(3.0)
+            //     .x(node.current_target.x)
+            //     .y(node.current_target.y)
+            //     .finish()
+            //     .unwrap();
+
+            // draw.ellipse()
+            //     .color(nannou::color::CHARCOAL)
+            //     .radius(3.0)
+            //     .x(node.box_center.x)
+            //     .y(node.box_center.y)
+            //     .finish()
+            //     .unwrap();
+
+            self.nodes.get(idx + (self.per_row)).map(|bottom| {
+                draw.line()
+                    .points(node.current_pos.into(), bottom.current_pos.into());
+            });
+
+            if (idx + 1) % self.per_row == 0 {
+                continue;
+            }
+
+            self.nodes.get(idx + 1).map(|right| {
+                draw.line()
+                    .points(node.current_pos.into(), right.current_pos.into());
+            });
+
+            // draw.ellipse()
+            //     .x(node.point.x)
+            //     .y(node.point.y)
+            //     .radius(2.0)
+            //     .finish()
+            //     .unwrap();
+        }
+    }
+
+    fn update(&mut self, ctx: &mut crate::Context, since_last: std::time::Duration) {
+        self.tick(since_last, &mut ctx.rng);
+    }
+}
+
+impl Net {
+    fn tick(&mut self, elapsed: std::time::Duration, rng: &mut SeededRng) {
+        for node in self.nodes.iter_mut() {
+            node.tick(elapsed, rng);
+        }
+    }
+}
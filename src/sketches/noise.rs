@@ -0,0 +1,39 @@
+use nannou::prelude::*;
+pub struct Model {
+    noise: nannou::noise::Perlin,
+    since_start: std::time::Duration,
+    points: Vec<Point2>,
+}
+
+impl crate::Sketch for Model {
+    fn new(ctx: &mut crate::Context) -> Self {
+        Model {
+            noise: crate::rng::perlin(&mut ctx.rng),
+            since_start: std::time::Duration::from_secs(0),
+            points: Vec::with_capacity(20_000),
+        }
+    }
+
+    fn update(&mut self, ctx: &mut crate::Context, since_last: std::time::Duration) {
+        use nannou::noise::NoiseFn;
+        self.since_start += since_last;
+        let x = self.since_start.as_millis() as f32 / 10.0 - ctx.rect.w() as f32 / 2.0;
+        let y = self.noise.get([x as f64 / 40.0, x as f64 / 83.0]) as f32 * ctx.rect.h() / 2.0;
+
+        self.points.push(Point2 { x, y });
+    }
+
+    fn view(&self, _ctx: &crate::Context, draw: &crate::Draw) {
+        use nannou::color;
+
+        draw.background().color(color::WHITE);
+
+        for (src, target) in self.points.iter().zip(self.points.iter().skip(1)) {
+            draw.line()
+                .thickness(3.0)
+                .color(color::BLACK)
+                .start(*src)
+                .end(*target);
+        }
+    }
+}
@@ -0,0 +1,623 @@
+use crate::automata::bitrow::BitRow;
+use crate::automata::elementary::Rule;
+use crate::automata::hashlife::Hashlife;
+use crate::automata::life::{Board, LifeRule, Neighbourhood};
+use crate::automata::rle::{self, Pattern};
+use crate::automata::Boundary;
+use crate::clock::FixedStep;
+use crate::palette::Ramp;
+use nannou::event::{Key, WindowEvent};
+use nannou::prelude::*;
+use rand::Rng;
+use std::collections::VecDeque;
+
+const TICK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// The intensity of a cell born in `Mode::Life`, then how much it brightens with every generation
+/// it survives.
+const GLOW_NEWBORN: f32 = 0.4;
+const GLOW_AGE_STEP: f32 = 0.1;
+
+/// How the points of the lattice show the automaton.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// The whole lattice is one long row, randomly perturbed at every tick.
+    Tape,
+    /// The top row of the lattice is the current generation, started from a single live cell, and
+    /// the rows below are the previous generations: a space-time diagram.
+    History,
+    /// The lattice is a two-dimensional Life-like automaton, older cells glowing brighter.
+    Life,
+    /// The same on an unbounded plane, stepped by Hashlife, the lattice showing a window of it.
+    Hashlife,
+}
+
+impl std::str::FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tape" => Ok(Mode::Tape),
+            "history" => Ok(Mode::History),
+            "life" => Ok(Mode::Life),
+            "hashlife" => Ok(Mode::Hashlife),
+            other => Err(format!(
+                "expected tape, history, life or hashlife, got {:?}",
+                other
+            )),
+        }
+    }
+}
+
+struct Point {
+    coord: Point2,
+    /// How lit the point is, from 0.0 to 1.0, following its state with some delay.
+    intensity: f32,
+    /// Generations alive in `Mode::Life` and `Mode::Hashlife`, 0 when dead.
+    age: u16,
+}
+
+/// How fast the intensity of a point follows its state, whatever the frame rate.
+#[derive(Debug, Clone, Copy)]
+struct Fade {
+    /// Time constants in seconds: the time to cover 63% of the way up to a lit state, and down to
+    /// an unlit one.
+    fade_in: f32,
+    fade_out: f32,
+}
+
+impl Fade {
+    /// Move `intensity` towards `target` as `since_last` passes, exponentially.
+    fn approach(self, intensity: f32, target: f32, since_last: std::time::Duration) -> f32 {
+        let time_constant = if target > intensity {
+            self.fade_in
+        } else {
+            self.fade_out
+        };
+
+        if time_constant <= 0.0 {
+            return target;
+        }
+
+        let progress = 1.0 - (-since_last.as_secs_f32() / time_constant).exp();
+        intensity + (target - intensity) * progress
+    }
+}
+
+/// From unlit to lit points.
+fn ramp() -> Ramp {
+    use nannou::color::Rgba;
+
+    Ramp::new(vec![
+        (0.0, Rgba::new(0.0, 0.0, 0.1, 0.5)),
+        (0.6, Rgba::new(0.4, 0.75, 1.0, 0.5)),
+        (1.0, Rgba::new(1.0, 1.0, 1.0, 0.5)),
+    ])
+}
+
+pub struct Lattice {
+    /// The distance between two points, in pixels.
+    gap: f32,
+    _w: f32,
+    _h: f32,
+    points: Vec<Point>,
+    /// The number of points in one row of the lattice.
+    columns: usize,
+    mode: Mode,
+    /// The current generation: one state per point in `Mode::Tape`, one per column in
+    /// `Mode::History`.
+    row: BitRow,
+    /// The previous generations in `Mode::History`, the most recent first, as many as the lattice
+    /// has rows below the current one.
+    history: VecDeque<BitRow>,
+    rule: Rule,
+    /// One cell per point in `Mode::Life`, and the visible window of `universe` in
+    /// `Mode::Hashlife`.
+    board: Board,
+    /// The whole plane in `Mode::Hashlife`, the lattice showing the cells from (0, 0) on. `None`
+    /// in the other modes.
+    universe: Option<Hashlife>,
+    /// Generations per tick in `Mode::Hashlife`.
+    hashlife_step: u64,
+    life_rule: LifeRule,
+    neighbourhood: Neighbourhood,
+    /// Loaded into the board instead of random cells.
+    pattern: Option<Pattern>,
+    /// Where the top left corner of `pattern` goes, centered when `None`.
+    pattern_offset: Option<(usize, usize)>,
+    boundary: Boundary,
+    /// Ticks since the last reset.
+    generation: u64,
+    fade: Fade,
+    ramp: Ramp,
+    clock: FixedStep,
+}
+
+impl crate::Sketch for Lattice {
+    const PARAMS: &'static [crate::Param] = &[
+        crate::Param {
+            name: "gap",
+            help: "distance between two points, in pixels",
+            default: "20",
+            check: crate::cli::positive::<f32>,
+        },
+        crate::Param {
+            name: "rule",
+            help: "Wolfram code of the elementary rule, 0 to 255",
+            default: "30",
+            check: crate::cli::parses::<u8>,
+        },
+        crate::Param {
+            name: "mode",
+            help:
+                "tape, the lattice as one long row, history, a space-time diagram, life or hashlife",
+            default: "tape",
+            check: crate::cli::parses::<Mode>,
+        },
+        crate::Param {
+            name: "life-rule",
+            help: "B/S rule of the life mode, or conway, highlife, seeds, day-and-night",
+            default: "B3/S23",
+            check: crate::cli::parses::<LifeRule>,
+        },
+        crate::Param {
+            name: "neighbourhood",
+            help: "neighbours of a cell in the life modes: moore or von-neumann",
+            default: "moore",
+            check: crate::cli::parses::<Neighbourhood>,
+        },
+        crate::Param {
+            name: "hashlife-step",
+            help: "generations per tick in the hashlife mode, which has no edges, up to 2^32",
+            default: "1",
+            check: check_hashlife_step,
+        },
+        crate::Param {
+            name: "rle",
+            help: "RLE pattern to run in the life mode, instead of random cells",
+            default: "",
+            check: check_pattern,
+        },
+        crate::Param {
+            name: "rle-offset",
+            help: "where the top left corner of the pattern goes, as x,y in cells, or center",
+            default: "center",
+            check: check_offset,
+        },
+        crate::Param {
+            name: "fade-in",
+            help: "seconds for a point to mostly light up",
+            default: "0.1",
+            check: crate::cli::parses::<f32>,
+        },
+        crate::Param {
+            name: "fade-out",
+            help: "seconds for a point to mostly fade out",
+            default: "0.8",
+            check: crate::cli::parses::<f32>,
+        },
+        crate::Param {
+            name: "boundary",
+            help: "what lies past the edges: periodic, dead, alive or reflective",
+            default: "dead",
+            check: crate::cli::parses::<Boundary>,
+        },
+    ];
+
+    fn new(ctx: &mut crate::Context) -> Self {
+        let window_rect = ctx.rect;
+        let gap: f32 = ctx.params.get("gap");
+        let (points, columns) = lattice(window_rect, gap);
+
+        let path: String = ctx.params.get("rle");
+        let pattern = if path.is_empty() {
+            None
+        } else {
+            let pattern = Pattern::load(path.as_ref())
+                .unwrap_or_else(|err| panic!("could not load {}: {}", path, err));
+            Some(pattern)
+        };
+
+        let offset: String = ctx.params.get("rle-offset");
+        let pattern_offset = parse_offset(&offset)
+            .unwrap_or_else(|| panic!("invalid value for --rle-offset: {:?}", offset));
+
+        let mut lattice = Lattice {
+            gap,
+            points,
+            columns,
+            mode: ctx.params.get("mode"),
+            row: BitRow::default(),
+            history: VecDeque::new(),
+            _w: window_rect.w(),
+            _h: window_rect.h(),
+            rule: Rule::new(ctx.params.get("rule")),
+            board: Board::default(),
+            universe: None,
+            hashlife_step: ctx.params.get("hashlife-step"),
+            life_rule: ctx.params.get("life-rule"),
+            neighbourhood: ctx.params.get("neighbourhood"),
+            pattern: None,
+            pattern_offset,
+            boundary: ctx.params.get("boundary"),
+            generation: 0,
+            fade: Fade {
+                fade_in: ctx.params.get("fade-in"),
+                fade_out: ctx.params.get("fade-out"),
+            },
+            ramp: ramp(),
+            clock: FixedStep::new(TICK_INTERVAL),
+        };
+
+        // A pattern only makes sense on a board, with the rule it was written for.
+        if let Some(pattern) = pattern {
+            if lattice.mode != Mode::Hashlife {
+                lattice.mode = Mode::Life;
+            }
+
+            if let Some(rule) = pattern.rule.as_ref() {
+                match rule.parse() {
+                    Ok(rule) => lattice.life_rule = rule,
+                    Err(err) => eprintln!("ignoring the rule of {}: {}", path, err),
+                }
+            }
+
+            lattice.pattern = Some(pattern);
+        }
+
+        lattice.reset(ctx);
+        lattice
+    }
+
+    fn view(&self, _ctx: &crate::Context, draw: &crate::Draw) {
+        draw.background().color(nannou::color::BLUE);
+
+        for point in self.points.iter() {
+            draw.ellipse()
+                .color(self.ramp.sample(point.intensity))
+                .h(self.gap * 1.4)
+                .w(self.gap * 1.4)
+                .x(point.coord.x)
+                .y(point.coord.y);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut crate::Context, since_last: std::time::Duration) {
+        for _ in 0..self.clock.advance(since_last) {
+            self.generation += 1;
+
+            match self.mode {
+                Mode::Tape => {
+                    for _ in 0..20 {
+                        let idx = ctx.rng.gen_range(0, self.points.len());
+                        self.row.toggle(idx);
+                    }
+
+                    self.row.step(self.rule, self.boundary);
+                }
+                Mode::History => {
+                    self.history.push_front(self.row.clone());
+                    self.history.truncate(self.rows() - 1);
+                    self.row.step(self.rule, self.boundary);
+                }
+                Mode::Life => {
+                    self.board
+                        .step(&self.life_rule, self.neighbourhood, self.boundary);
+                    self.age();
+                }
+                Mode::Hashlife => {
+                    let rows = self.rows();
+
+                    if let Some(universe) = self.universe.as_mut() {
+                        universe.step(self.hashlife_step);
+                        self.board = universe.window(0, 0, self.columns, rows);
+                    }
+                    self.age();
+                }
+            }
+        }
+
+        let fade = self.fade;
+
+        for idx in 0..self.points.len() {
+            let target = self.target(idx);
+            let point = &mut self.points[idx];
+
+            point.intensity = match self.mode {
+                // The rows below already show the past, fading would only blur them.
+                Mode::History => target,
+                Mode::Tape | Mode::Life | Mode::Hashlife => {
+                    fade.approach(point.intensity, target, since_last)
+                }
+            };
+        }
+    }
+
+    fn event(&mut self, ctx: &mut crate::Context, event: &WindowEvent) {
+        match event {
+            WindowEvent::KeyPressed(Key::M) => {
+                self.mode = match self.mode {
+                    Mode::Tape => Mode::History,
+                    Mode::History => Mode::Life,
+                    Mode::Life => Mode::Hashlife,
+                    Mode::Hashlife => Mode::Tape,
+                };
+                self.reset(ctx);
+                return;
+            }
+            WindowEvent::KeyPressed(Key::E) => {
+                self.export(ctx);
+                return;
+            }
+            WindowEvent::KeyPressed(Key::L) => {
+                let presets = LifeRule::PRESETS;
+                let current = presets
+                    .iter()
+                    .position(|(_, rule)| rule.parse() == Ok(self.life_rule));
+                let (name, rule) = presets[current.map(|idx| idx + 1).unwrap_or(0) % presets.len()];
+
+                eprintln!("{} ({})", name, rule);
+                self.life_rule = rule.parse().unwrap();
+
+                if let Some(universe) = self.universe.as_mut() {
+                    if let Err(err) = universe.set_rule(self.life_rule) {
+                        eprintln!("{}, carrying on in the life mode", err);
+                        self.mode = Mode::Life;
+                        self.universe = None;
+                    }
+                }
+                return;
+            }
+            _ => (),
+        }
+
+        let rule = match event {
+            WindowEvent::KeyPressed(Key::N) => self.rule.next_notable(),
+            WindowEvent::KeyPressed(Key::Right) => Rule::new(self.rule.number().wrapping_add(1)),
+            WindowEvent::KeyPressed(Key::Left) => Rule::new(self.rule.number().wrapping_sub(1)),
+            _ => return,
+        };
+
+        eprintln!("{}", rule);
+        self.rule = rule;
+    }
+
+    fn clock(&mut self) -> Option<&mut FixedStep> {
+        Some(&mut self.clock)
+    }
+
+    /// Lay the lattice out again over the new window, and start over.
+    fn resized(&mut self, ctx: &mut crate::Context) {
+        let (points, columns) = lattice(ctx.rect, self.gap);
+        self.points = points;
+        self.columns = columns;
+        self._w = ctx.rect.w();
+        self._h = ctx.rect.h();
+
+        self.reset(ctx);
+    }
+}
+
+/// Points `gap` apart in full rows covering `window_rect`, from its top left corner, and the
+/// number of points in a row.
+fn lattice(window_rect: Rect, gap: f32) -> (Vec<Point>, usize) {
+    let mut cursor: Point2 = window_rect.top_left();
+
+    let locations = std::iter::once(cursor).chain(std::iter::from_fn(|| {
+        if cursor.x > window_rect.right() {
+            cursor.x = window_rect.left();
+            cursor.y -= gap;
+            return Some(cursor);
+        }
+
+        if cursor.y < window_rect.bottom() {
+            return None;
+        }
+
+        cursor.x += gap;
+        Some(cursor)
+    }));
+
+    let mut points: Vec<Point> = locations
+        .map(|location| Point {
+            coord: location,
+            intensity: 0.0,
+            age: 0,
+        })
+        .collect();
+
+    let top = window_rect.top();
+    let columns = points
+        .iter()
+        .take_while(|point| point.coord.y == top)
+        .count();
+
+    // The locations end with the first point of a row past the bottom of the window.
+    points.truncate(points.len() / columns * columns);
+
+    (points, columns)
+}
+
+impl Lattice {
+    /// The number of rows of the lattice.
+    fn rows(&self) -> usize {
+        self.points.len() / self.columns
+    }
+
+    /// Start over from the initial generation of the current mode.
+    fn reset(&mut self, ctx: &mut crate::Context) {
+        self.history.clear();
+        self.generation = 0;
+        self.universe = None;
+
+        if self.mode == Mode::Hashlife {
+            match Hashlife::new(self.life_rule, self.neighbourhood) {
+                Ok(universe) => self.universe = Some(universe),
+                Err(err) => {
+                    eprintln!("{}, running the life mode instead", err);
+                    self.mode = Mode::Life;
+                }
+            }
+        }
+
+        match self.mode {
+            Mode::Tape => {
+                self.row = BitRow::new(self.points.len());
+
+                for _ in 1..(self.points.len() / 5) {
+                    let idx = ctx.rng.gen_range(0, self.points.len());
+                    self.row.set(idx, true);
+                }
+            }
+            Mode::History => {
+                self.row = BitRow::new(self.columns);
+                self.row.set(self.columns / 2, true);
+            }
+            Mode::Life | Mode::Hashlife => {
+                self.board = Board::new(self.columns, self.rows());
+
+                if let Some(pattern) = self.pattern.as_ref() {
+                    let (x, y) = self
+                        .pattern_offset
+                        .unwrap_or_else(|| pattern.centered_on(&self.board));
+                    pattern.place(&mut self.board, x, y);
+                } else {
+                    for _ in 1..(self.points.len() / 5) {
+                        let idx = ctx.rng.gen_range(0, self.points.len());
+                        self.board.set(idx % self.columns, idx / self.columns, true);
+                    }
+                }
+
+                if let Some(universe) = self.universe.as_mut() {
+                    for (idx, &alive) in self.board.cells().iter().enumerate() {
+                        if alive {
+                            let (x, y) = (idx % self.columns, idx / self.columns);
+                            universe.set(x as i64, y as i64, true);
+                        }
+                    }
+                }
+
+                for point in self.points.iter_mut() {
+                    point.age = 0;
+                }
+                self.age();
+            }
+        }
+    }
+
+    /// Count one more generation for the live cells of `Mode::Life` and `Mode::Hashlife`.
+    fn age(&mut self) {
+        for (point, &alive) in self.points.iter_mut().zip(self.board.cells()) {
+            point.age = if alive {
+                point.age.saturating_add(1)
+            } else {
+                0
+            };
+        }
+    }
+
+    /// The intensity the point at `idx` fades towards: lit when alive, and in `Mode::Life` the
+    /// brighter the older.
+    fn target(&self, idx: usize) -> f32 {
+        match self.mode {
+            Mode::Life | Mode::Hashlife => match self.points[idx].age {
+                0 => 0.0,
+                age => (GLOW_NEWBORN + GLOW_AGE_STEP * f32::from(age - 1)).min(1.0),
+            },
+            Mode::Tape | Mode::History => {
+                if self.state(idx) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// Whether the point at `idx` is alive.
+    fn state(&self, idx: usize) -> bool {
+        match self.mode {
+            Mode::Tape => self.row.get(idx),
+            Mode::Life | Mode::Hashlife => self.board.cells()[idx],
+            Mode::History => {
+                let (row, column) = (idx / self.columns, idx % self.columns);
+
+                match row {
+                    0 => self.row.get(column),
+                    _ => self
+                        .history
+                        .get(row - 1)
+                        .map(|cells| cells.get(column))
+                        .unwrap_or(false),
+                }
+            }
+        }
+    }
+
+    /// The lattice as a board, whatever the mode.
+    fn snapshot(&self) -> Board {
+        let mut board = Board::new(self.columns, self.rows());
+
+        for idx in 0..self.points.len() {
+            board.set(idx % self.columns, idx / self.columns, self.state(idx));
+        }
+
+        board
+    }
+
+    /// Write the lattice as an RLE pattern, e.g. `rule_30-1234-000042.rle`.
+    fn export(&self, ctx: &crate::Context) {
+        let path = format!("rule_30-{}-{:06}.rle", ctx.seed, self.generation);
+        let rule = match self.mode {
+            Mode::Life | Mode::Hashlife => Some(&self.life_rule),
+            Mode::Tape | Mode::History => None,
+        };
+
+        match std::fs::write(&path, rle::encode(&self.snapshot(), rule)) {
+            Ok(()) => eprintln!("saved {}", path),
+            Err(err) => eprintln!("could not save {}: {}", path, err),
+        }
+    }
+}
+
+fn check_hashlife_step(value: &str) -> Result<(), String> {
+    let step: u64 = value.parse().map_err(|err| format!("{}", err))?;
+
+    if step > Hashlife::MAX_STEP {
+        return Err(format!(
+            "at most {} generations per tick",
+            Hashlife::MAX_STEP
+        ));
+    }
+
+    Ok(())
+}
+
+/// Whether the `--rle` file, if any, can be read and decoded.
+fn check_pattern(path: &str) -> Result<(), String> {
+    if path.is_empty() {
+        return Ok(());
+    }
+
+    Pattern::load(path.as_ref())
+        .map(drop)
+        .map_err(|err| err.to_string())
+}
+
+fn check_offset(value: &str) -> Result<(), String> {
+    parse_offset(value)
+        .map(drop)
+        .ok_or_else(|| "expected x,y in cells, or center".to_owned())
+}
+
+/// Parse an `--rle-offset`: `None` to center the pattern.
+fn parse_offset(value: &str) -> Option<Option<(usize, usize)>> {
+    if value == "center" {
+        return Some(None);
+    }
+
+    let mut parts = value.splitn(2, ',');
+    let x = parts.next()?.trim().parse().ok()?;
+    let y = parts.next()?.trim().parse().ok()?;
+    Some(Some((x, y)))
+}
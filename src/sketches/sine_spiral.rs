@@ -0,0 +1,57 @@
+use nannou::prelude::*;
+
+pub struct Model {
+    since_start: std::time::Duration,
+    cursor: Point2,
+    angle: f32,
+    poses: Vec<Point2>,
+}
+
+impl crate::Sketch for Model {
+    fn new(_ctx: &mut crate::Context) -> Self {
+        Model {
+            since_start: std::time::Duration::from_secs(0),
+            cursor: Point2 { x: 0.0, y: 0.0 },
+            angle: 0.0,
+            poses: Vec::with_capacity(20_000),
+        }
+    }
+
+    fn update(&mut self, _ctx: &mut crate::Context, since_last: std::time::Duration) {
+        self.since_start += since_last;
+        self.move_cursor(self.since_start, since_last)
+    }
+
+    fn view(&self, _ctx: &crate::Context, draw: &crate::Draw) {
+        draw.background().color(nannou::color::BLUE);
+
+        for (origin, target) in (0..self.poses.len()).zip(1..self.poses.len()) {
+            draw.line()
+                .start(self.poses[origin])
+                .end(self.poses[target])
+                .thickness(5.0)
+                .color(nannou::color::WHITE);
+        }
+    }
+}
+
+impl Model {
+    fn move_cursor(&mut self, elapsed: std::time::Duration, since_last: std::time::Duration) {
+        let radius = elapsed.as_millis() as f32 / 100.0;
+        self.angle += since_last.as_millis() as f32 / ((radius + 50.0) * 2.0);
+
+        let drawn_radius = radius + (elapsed.as_micros() as f32 / 30000.0).sin() * (radius / 16.0);
+        self.poses.push(Point2 {
+            x: drawn_radius * self.angle.cos(),
+            y: drawn_radius * self.angle.sin(),
+        });
+
+        // angle.sin() = y / radius
+        // angle.cos() = a / radius
+
+        self.cursor = Point2 {
+            x: radius * self.angle.cos(),
+            y: radius * self.angle.sin(),
+        }
+    }
+}
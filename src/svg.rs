@@ -0,0 +1,230 @@
+//! Serialise recorded frames to SVG.
+
+use crate::draw::{Color, Placement, Primitive};
+use nannou::geom::{Point2, Rect};
+use std::fmt::Write;
+
+/// An SVG document showing `primitives` as drawn in a window covering `rect`.
+pub fn render(primitives: &[Primitive], rect: Rect) -> String {
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = rect.w(),
+        h = rect.h(),
+    );
+
+    // nannou has the origin in the middle of the window and y pointing up, SVG has it in the top
+    // left corner and y pointing down.
+    let to_svg = |point: Point2| (point.x - rect.left(), rect.top() - point.y);
+
+    for primitive in primitives {
+        match primitive {
+            Primitive::Background(background) => writeln!(
+                out,
+                "  <rect x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" {}/>",
+                rect.w(),
+                rect.h(),
+                fill(background.color),
+            ),
+            Primitive::Ellipse(ellipse) => {
+                let placement = &ellipse.placement;
+                let (cx, cy) = to_svg(placement.center);
+                writeln!(
+                    out,
+                    "  <ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\"{} {}/>",
+                    cx,
+                    cy,
+                    placement.w.abs() / 2.0,
+                    placement.h.abs() / 2.0,
+                    rotation(placement, (cx, cy)),
+                    fill(ellipse.color),
+                )
+            }
+            Primitive::Rect(rectangle) => {
+                let placement = &rectangle.placement;
+                let (cx, cy) = to_svg(placement.center);
+                writeln!(
+                    out,
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"{} {}/>",
+                    cx - placement.w.abs() / 2.0,
+                    cy - placement.h.abs() / 2.0,
+                    placement.w.abs(),
+                    placement.h.abs(),
+                    rotation(placement, (cx, cy)),
+                    fill(rectangle.color),
+                )
+            }
+            Primitive::Line(line) => {
+                let (x1, y1) = to_svg(line.start);
+                let (x2, y2) = to_svg(line.end);
+                writeln!(
+                    out,
+                    "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke-width=\"{}\" {}/>",
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    line.thickness,
+                    stroke(line.color),
+                )
+            }
+            Primitive::Tri(tri) => writeln!(
+                out,
+                "  <polygon points=\"{}\" {}/>",
+                points(tri.points.iter().map(|point| to_svg(*point))),
+                fill(tri.color),
+            ),
+            Primitive::Polygon(polygon) => writeln!(
+                out,
+                "  <polygon points=\"{}\" {}/>",
+                points(polygon.points.iter().map(|point| to_svg(*point))),
+                fill(polygon.color),
+            ),
+        }
+        .expect("writing to a String");
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Write the SVG for `primitives` to `path`.
+pub fn save<P: AsRef<std::path::Path>>(
+    path: P,
+    primitives: &[Primitive],
+    rect: Rect,
+) -> std::io::Result<()> {
+    std::fs::write(path, render(primitives, rect))
+}
+
+/// The transform attribute for a rotated placement, empty when there is no rotation.
+fn rotation(placement: &Placement, (cx, cy): (f32, f32)) -> String {
+    if placement.rotation == 0.0 {
+        return String::new();
+    }
+
+    // The y axis is flipped, so counter-clockwise in nannou is clockwise in SVG.
+    format!(
+        " transform=\"rotate({} {} {})\"",
+        -placement.rotation.to_degrees(),
+        cx,
+        cy
+    )
+}
+
+fn points<I: Iterator<Item = (f32, f32)>>(points: I) -> String {
+    points
+        .map(|(x, y)| format!("{},{}", x, y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn fill(color: Color) -> String {
+    format!(
+        "fill=\"{}\" fill-opacity=\"{}\"",
+        rgb(color),
+        channel(color.alpha)
+    )
+}
+
+fn stroke(color: Color) -> String {
+    format!(
+        "fill=\"none\" stroke=\"{}\" stroke-opacity=\"{}\"",
+        rgb(color),
+        channel(color.alpha)
+    )
+}
+
+fn rgb(color: Color) -> String {
+    let to_u8 = |channel: f32| (self::channel(channel) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        to_u8(color.red),
+        to_u8(color.green),
+        to_u8(color.blue)
+    )
+}
+
+/// Color channels are expected in `0.0..=1.0`.
+fn channel(value: f32) -> f32 {
+    value.clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw::Draw;
+    use nannou::color::Rgba;
+
+    fn render_with(rect: Rect, draw: impl FnOnce(&Draw)) -> Vec<String> {
+        let recording = Draw::new();
+        draw(&recording);
+
+        render(&recording.into_primitives(), rect)
+            .lines()
+            .map(str::to_owned)
+            .collect()
+    }
+
+    #[test]
+    fn document() {
+        let lines = render_with(Rect::from_w_h(200.0, 100.0), |draw| {
+            draw.background().color(Rgba::new(0.0, 0.0, 1.0, 1.0));
+        });
+
+        assert_eq!(
+            lines,
+            [
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\" height=\"100\" viewBox=\"0 0 200 100\">",
+                "  <rect x=\"0\" y=\"0\" width=\"200\" height=\"100\" fill=\"#0000ff\" fill-opacity=\"1\"/>",
+                "</svg>",
+            ]
+        );
+    }
+
+    #[test]
+    fn rect() {
+        let lines = render_with(Rect::from_w_h(200.0, 100.0), |draw| {
+            // Above and to the right of the middle of the window.
+            draw.rect()
+                .x_y(10.0, 20.0)
+                .w_h(40.0, 20.0)
+                .color(Rgba::new(1.0, 0.0, 0.0, 0.5));
+            draw.rect()
+                .x_y(10.0, 20.0)
+                .w_h(40.0, 20.0)
+                .rotate(std::f32::consts::FRAC_PI_2)
+                .color(Rgba::new(0.0, 1.0, 0.0, 2.0));
+        });
+
+        assert_eq!(
+            lines[1],
+            "  <rect x=\"90\" y=\"20\" width=\"40\" height=\"20\" fill=\"#ff0000\" fill-opacity=\"0.5\"/>"
+        );
+        // A quarter turn counter-clockwise is a quarter turn clockwise with y pointing down, and
+        // out of range channels are clamped.
+        assert_eq!(
+            lines[2],
+            "  <rect x=\"90\" y=\"20\" width=\"40\" height=\"20\" transform=\"rotate(-90 110 30)\" fill=\"#00ff00\" fill-opacity=\"1\"/>"
+        );
+    }
+
+    #[test]
+    fn polygon() {
+        let lines = render_with(Rect::from_w_h(200.0, 100.0), |draw| {
+            let corners = vec![
+                Point2 { x: -100.0, y: 50.0 },
+                Point2 { x: 100.0, y: 50.0 },
+                Point2 { x: 0.0, y: -50.0 },
+            ];
+            draw.polygon()
+                .points(corners)
+                .color(Rgba::new(0.2, 0.4, 0.6, 1.0));
+        });
+
+        // The top corners of the window, then the middle of its bottom edge.
+        assert_eq!(
+            lines[1],
+            "  <polygon points=\"0,0 200,0 100,100\" fill=\"#336699\" fill-opacity=\"1\"/>"
+        );
+    }
+}